@@ -0,0 +1,465 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+#[cfg(not(feature = "tracing"))]
+use log::debug;
+#[cfg(feature = "tracing")]
+use tracing::debug;
+
+use crate::utils::{body_error, check_status, request_error, validate_endpoint};
+use crate::{FactorDbError, Number, ENDPOINT, USER_AGENT};
+
+/// Blocking API client for FactorDB API.
+///
+/// If you're making multiple requests, it's probably a good idea to reuse the client to take advantage of keep-alive
+/// connection pooling. ([Learn more](https://docs.rs/reqwest/latest/reqwest/index.html#making-a-get-request))
+///
+/// As per [`reqwest::blocking`] restriction, this client must not be used in an async runtime. Please use
+/// [`FactorDbClient`] for that.
+///
+/// Internally, the blocking client runs its own runtime on a background thread. Dropping the client (and all of its
+/// clones) shuts that runtime down and joins the thread, so no tasks or connections outlive the client.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use factordb::FactorDbBlockingClient;
+/// use num_bigint::BigInt; // All numeric values in the result object are of this type
+///
+/// fn main() -> Result<(), Box<dyn Error>> {
+/// // Initialise the client
+/// let client = FactorDbBlockingClient::new();
+///
+/// // Make requests
+/// let forty_two = client.get(42)?;
+/// let expect_factors: Vec<BigInt> = vec![2, 3, 7].into_iter().map(|n| BigInt::from(n)).collect();
+/// assert_eq!(forty_two.into_factors_flattened(), expect_factors);
+/// #
+/// #   Ok(())
+/// # }
+#[derive(Debug, Clone)]
+pub struct FactorDbBlockingClient {
+    client: reqwest::blocking::Client,
+    endpoint: String,
+}
+
+impl FactorDbBlockingClient {
+    /// Creates a new instance of [`FactorDbBlockingClient`] with a default HTTP client, which identifies itself to
+    /// FactorDB with a `User-Agent` of `factordb-rust/<version>`.
+    pub fn new() -> Self {
+        let client = reqwest::blocking::Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("failed to create HTTP client");
+        Self::with_client(client)
+    }
+
+    /// Creates a [`FactorDbBlockingClientBuilder`] to configure a new client.
+    pub fn builder() -> FactorDbBlockingClientBuilder {
+        FactorDbBlockingClientBuilder::new()
+    }
+
+    /// Creates a new instance of [`FactorDbBlockingClient`] with a supplied [`reqwest::Client`].
+    ///
+    /// The `User-Agent` header is left to `client`, so set one with
+    /// [`reqwest::blocking::ClientBuilder::user_agent()`].
+    pub fn with_client(client: reqwest::blocking::Client) -> Self {
+        debug!("Creating blocking HTTP client");
+        Self {
+            client,
+            endpoint: ENDPOINT.to_string(),
+        }
+    }
+
+    /// Creates a new instance of [`FactorDbBlockingClient`] with a default HTTP client, sending its requests to
+    /// `endpoint` instead of `http://factordb.com/api`.
+    ///
+    /// This can be used to connect over HTTPS (`https://factordb.com/api`) or to a mirror of FactorDB.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::InvalidEndpoint`] if `endpoint` isn't a valid HTTP(S) URL.
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Result<Self, FactorDbError> {
+        Ok(Self {
+            endpoint: validate_endpoint(endpoint.into())?,
+            ..Self::new()
+        })
+    }
+
+    /// Sends a GET request to the FactorDB API for a given number. Returns an instance of [`Factor`].
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub fn get<T: Display>(&self, number: T) -> Result<Number, FactorDbError> {
+        record(|| parse_number(self.fetch_response(number, None)?))
+    }
+
+    /// Same as [`Self::get()`], but gives up if the request doesn't complete within `timeout`.
+    ///
+    /// This overrides the client's timeout (30 seconds unless configured otherwise) for this request only.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::Timeout`] if the request times out, or another [`FactorDbError`] if either the API
+    /// request responded with an error or there is an error in the request or parsing of the response.
+    pub fn get_with_timeout<T: Display>(
+        &self,
+        number: T,
+        timeout: Duration,
+    ) -> Result<Number, FactorDbError> {
+        record(|| parse_number(self.fetch_response(number, Some(timeout))?))
+    }
+
+    /// Looks up the FactorDB entry with the given ID, as returned by [`Number::id()`].
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub fn get_by_id<T: Display>(&self, id: T) -> Result<Number, FactorDbError> {
+        record(|| parse_number(self.send(format!("{}?id={}", self.endpoint, id), None)?))
+    }
+
+    /// Sends a GET request to the FactorDB API for a given number and returns its JSON response.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub fn get_json<T: Display>(&self, number: T) -> Result<String, FactorDbError> {
+        record(|| {
+            self.fetch_response(number, None)?
+                .text()
+                .map_err(body_error)
+        })
+    }
+
+    /// Make the actual web request, optionally overriding the client's timeout
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(query = %number, status), err(level = "debug"))
+    )]
+    fn fetch_response<T: Display>(
+        &self,
+        number: T,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::blocking::Response, FactorDbError> {
+        let response = self.send(format!("{}?query={}", self.endpoint, number), timeout)?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("status", response.status().as_u16());
+        Ok(response)
+    }
+
+    /// Sends a request to `url`, turning unsuccessful responses into errors.
+    fn send(
+        &self,
+        url: String,
+        timeout: Option<Duration>,
+    ) -> Result<reqwest::blocking::Response, FactorDbError> {
+        debug!("Fetching API response from {}", url);
+        let mut request = self.client.get(url);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send().map_err(request_error)?;
+        check_status(response.status(), response.headers())?;
+        Ok(response)
+    }
+}
+
+/// Reads the body of `response` and parses it as a [`Number`].
+fn parse_number(response: reqwest::blocking::Response) -> Result<Number, FactorDbError> {
+    let body = response.bytes().map_err(body_error)?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Runs `request`, which sends a request and reads its response, recording it in the metrics if enabled.
+///
+/// This covers reading and parsing the body as well, so that errors there are counted too.
+fn record<R>(request: impl FnOnce() -> Result<R, FactorDbError>) -> Result<R, FactorDbError> {
+    #[cfg(feature = "metrics")]
+    let start = std::time::Instant::now();
+    let result = request();
+    #[cfg(feature = "metrics")]
+    crate::telemetry::record_request(start.elapsed(), result.as_ref().err());
+    result
+}
+
+impl Default for FactorDbBlockingClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A builder to configure a [`FactorDbBlockingClient`], the blocking counterpart of
+/// [`FactorDbClientBuilder`](crate::FactorDbClientBuilder).
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use std::time::Duration;
+///
+/// use factordb::FactorDbBlockingClient;
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// let client = FactorDbBlockingClient::builder()
+///     .endpoint("https://factordb.com/api")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .build()?;
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct FactorDbBlockingClientBuilder {
+    endpoint: Option<String>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<String>,
+}
+
+impl FactorDbBlockingClientBuilder {
+    /// Creates a new builder with the default configuration, equivalent to [`FactorDbBlockingClient::new()`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends requests to `endpoint` instead of `http://factordb.com/api`, see
+    /// [`FactorDbBlockingClient::with_endpoint()`].
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Gives up on requests which haven't completed within `timeout`, returning [`FactorDbError::Timeout`]. By default,
+    /// requests time out after 30 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends `user_agent` as the `User-Agent` header of every request instead of `factordb-rust/<version>`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sends all requests through the proxy at `url`, e.g. `http://localhost:8080` or `socks5://localhost:1080`.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Builds the [`FactorDbBlockingClient`].
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::InvalidEndpoint`] if the endpoint isn't a valid HTTP(S) URL, or another
+    /// [`FactorDbError`] if the underlying HTTP client can't be built (e.g. because of an invalid proxy URL).
+    pub fn build(self) -> Result<FactorDbBlockingClient, FactorDbError> {
+        let endpoint = match self.endpoint {
+            Some(endpoint) => validate_endpoint(endpoint)?,
+            None => ENDPOINT.to_string(),
+        };
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder = match self.user_agent {
+            Some(user_agent) => builder.user_agent(user_agent),
+            None => builder.user_agent(USER_AGENT),
+        };
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        let client = builder.build()?;
+        debug!("Creating blocking HTTP client");
+        Ok(FactorDbBlockingClient { client, endpoint })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    use super::*;
+
+    #[test]
+    fn test_two_factors_blocking() {
+        let client = FactorDbBlockingClient::new();
+        let result = client.get(15).unwrap();
+        assert_eq!(
+            vec![BigInt::from(3), BigInt::from(5)],
+            result.into_factors_flattened()
+        )
+    }
+
+    #[test]
+    fn test_repeating_factors_blocking() {
+        let client = FactorDbBlockingClient::new();
+        let result = client.get(100).unwrap();
+        assert_eq!(
+            vec![
+                BigInt::from(2),
+                BigInt::from(2),
+                BigInt::from(5),
+                BigInt::from(5)
+            ],
+            result.clone().into_factors_flattened()
+        );
+        assert_eq!(
+            vec![BigInt::from(2), BigInt::from(5)],
+            result.into_unique_factors()
+        );
+    }
+
+    #[test]
+    fn test_prime_blocking() {
+        let client = FactorDbBlockingClient::new();
+        let result = client.get(17).unwrap();
+        let flatenned = result.clone().into_factors_flattened();
+        let unique = result.into_unique_factors();
+        assert_eq!(vec![BigInt::from(17)], flatenned);
+        assert_eq!(flatenned, unique);
+    }
+
+    #[test]
+    fn test_invalid_blocking() {
+        let client = FactorDbBlockingClient::new();
+        let result = client.get("AAAAA");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_with_endpoint_blocking() {
+        let mut server = mockito::Server::new();
+        crate::test_utils::mock_query(&mut server, "15", &crate::test_utils::entry(15)).create();
+        let client =
+            FactorDbBlockingClient::with_endpoint(format!("{}/api", server.url())).unwrap();
+        assert_eq!(
+            client.get(15).unwrap().into_factors_flattened(),
+            vec![BigInt::from(3), BigInt::from(5)]
+        );
+        assert!(matches!(
+            FactorDbBlockingClient::with_endpoint("factordb.com/api"),
+            Err(FactorDbError::InvalidEndpoint(_))
+        ));
+    }
+
+    #[test]
+    fn test_builder_blocking() {
+        let mut server = mockito::Server::new();
+        crate::test_utils::mock_query(&mut server, "15", &crate::test_utils::entry(15))
+            .match_header("user-agent", "factordb-test")
+            .create();
+        let client = FactorDbBlockingClient::builder()
+            .endpoint(format!("{}/api", server.url()))
+            .timeout(Duration::from_secs(10))
+            .user_agent("factordb-test")
+            .build()
+            .unwrap();
+        assert_eq!(client.get(15).unwrap().value(), BigInt::from(15));
+        assert!(matches!(
+            FactorDbBlockingClient::builder()
+                .endpoint("factordb.com/api")
+                .build(),
+            Err(FactorDbError::InvalidEndpoint(_))
+        ));
+    }
+
+    #[test]
+    fn test_user_agent_blocking() {
+        let mut server = mockito::Server::new();
+        let mock = crate::test_utils::mock_query(&mut server, "15", &crate::test_utils::entry(15))
+            .match_header("user-agent", USER_AGENT)
+            .expect(2)
+            .create();
+        let endpoint = format!("{}/api", server.url());
+        let clients = [
+            FactorDbBlockingClient::with_endpoint(&endpoint).unwrap(),
+            FactorDbBlockingClient::builder()
+                .endpoint(endpoint)
+                .build()
+                .unwrap(),
+        ];
+        for client in clients {
+            client.get(15).unwrap();
+        }
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_by_id_blocking() {
+        let mut server = mockito::Server::new();
+        let id = "1100000000000000017";
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::UrlEncoded("id".into(), id.into()))
+            .with_header("content-type", "application/json")
+            .with_body(format!(
+                r#"{{"id":"{}","status":"P","factors":[["17",1]]}}"#,
+                id
+            ))
+            .create();
+        let client =
+            FactorDbBlockingClient::with_endpoint(format!("{}/api", server.url())).unwrap();
+        let number = client.get_by_id(id).unwrap();
+        assert_eq!(number.id().to_string(), id);
+        assert_eq!(client.get_by_id(number.id()).unwrap(), number);
+        assert!(matches!(
+            client.get_by_id("1100000000000000000"),
+            Err(FactorDbError::ServerError(_))
+        ));
+    }
+
+    #[test]
+    fn test_error_statuses_blocking() {
+        let mut server = mockito::Server::new();
+        for (query, status) in [("404", 404), ("429", 429), ("503", 503)] {
+            crate::test_utils::mock_query(&mut server, query, "")
+                .with_status(status)
+                .create();
+        }
+        let client =
+            FactorDbBlockingClient::with_endpoint(format!("{}/api", server.url())).unwrap();
+        assert!(matches!(client.get(404), Err(FactorDbError::InvalidNumber)));
+        assert!(matches!(
+            client.get(429),
+            Err(FactorDbError::RateLimited { retry_after: None })
+        ));
+        assert!(matches!(
+            client.get_json(503),
+            Err(FactorDbError::ServerError(status)) if status == 503
+        ));
+    }
+
+    #[test]
+    fn test_get_with_timeout_blocking() {
+        // accepts connections (through the backlog) but never responds
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/api", listener.local_addr().unwrap());
+        let client = FactorDbBlockingClient::with_endpoint(endpoint).unwrap();
+        assert!(matches!(
+            client.get_with_timeout(15, Duration::from_millis(100)),
+            Err(FactorDbError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_response_blocking() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_body("<html><body>Something went wrong</body></html>")
+            .create();
+        let client =
+            FactorDbBlockingClient::with_endpoint(format!("{}/api", server.url())).unwrap();
+        assert!(matches!(client.get(15), Err(FactorDbError::ParseError(_))));
+    }
+
+    #[test]
+    fn test_send_sync_blocking() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<FactorDbBlockingClient>();
+    }
+}
@@ -0,0 +1,148 @@
+use std::fmt::Display;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use lru::LruCache;
+
+use crate::{FactorDbClient, FactorDbError, Number};
+
+/// Whether a result was served from the cache of a [`CachedFactorDbClient`] or fetched from FactorDB.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CacheStatus {
+    /// The result was in the cache, so no request was made.
+    Hit,
+    /// The result wasn't in the cache and was fetched from FactorDB.
+    Miss,
+}
+
+/// A [`FactorDbClient`] which keeps the most recently looked up numbers in memory, so that repeated lookups of the
+/// same number don't go over the network again.
+///
+/// Entries are keyed by the query as passed to [`Self::get()`], so e.g. `"1024"` and `"2^10"` are cached separately.
+/// Failed lookups aren't cached. Clones share the same cache.
+///
+/// # Examples
+/// ```no_run
+/// # use std::error::Error;
+/// use std::num::NonZeroUsize;
+/// use factordb::{CachedFactorDbClient, FactorDbClient};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let client = CachedFactorDbClient::with_capacity(FactorDbClient::new(), NonZeroUsize::new(1000).unwrap());
+///
+/// let first = client.get(42).await?; // fetched from FactorDB
+/// let second = client.get(42).await?; // served from the cache
+/// assert_eq!(first, second);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CachedFactorDbClient {
+    client: FactorDbClient,
+    cache: Arc<Mutex<LruCache<String, Number>>>,
+}
+
+impl CachedFactorDbClient {
+    /// Wraps `client` with a cache holding up to `capacity` numbers, evicting the least recently used one when full.
+    pub fn with_capacity(client: FactorDbClient, capacity: NonZeroUsize) -> Self {
+        Self {
+            client,
+            cache: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Same as [`FactorDbClient::get()`], but served from the cache if `number` has been looked up before.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if the number isn't cached and either the API request responded with an error or
+    /// there is an error in the request or parsing of the response.
+    pub async fn get<T: Display>(&self, number: T) -> Result<Number, FactorDbError> {
+        let (number, _) = self.get_cached_info(number).await?;
+        Ok(number)
+    }
+
+    /// Same as [`Self::get()`], but also returns whether the result came from the cache.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if the number isn't cached and either the API request responded with an error or
+    /// there is an error in the request or parsing of the response.
+    pub async fn get_cached_info<T: Display>(
+        &self,
+        number: T,
+    ) -> Result<(Number, CacheStatus), FactorDbError> {
+        let query = number.to_string();
+        let cached = self.lock().get(&query).cloned();
+        let status = if cached.is_some() {
+            CacheStatus::Hit
+        } else {
+            CacheStatus::Miss
+        };
+        #[cfg(feature = "metrics")]
+        crate::telemetry::record_cache(status);
+        if let Some(number) = cached {
+            return Ok((number, status));
+        }
+        let number = self.client.get(&query).await?;
+        self.lock().put(query, number.clone());
+        Ok((number, status))
+    }
+
+    /// Removes all numbers from the cache, e.g. to pick up progress FactorDB has made on factoring them.
+    pub fn clear_cache(&self) {
+        self.lock().clear();
+    }
+
+    /// Returns the wrapped [`FactorDbClient`], e.g. to bypass the cache.
+    pub fn client(&self) -> &FactorDbClient {
+        &self.client
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruCache<String, Number>> {
+        // the cache is never left in an inconsistent state, so a panic while holding the lock doesn't matter
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[tokio::test]
+    async fn test_get_cached_info() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let client = CachedFactorDbClient::with_capacity(
+            test_utils::client(&server),
+            NonZeroUsize::new(10).unwrap(),
+        );
+        let (first, status) = client.get_cached_info(15).await.unwrap();
+        assert_eq!(status, CacheStatus::Miss);
+        let (second, status) = client.get_cached_info(15).await.unwrap();
+        assert_eq!(status, CacheStatus::Hit);
+        assert_eq!(first, second);
+        assert!(client.get_cached_info("bad").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_no_request_on_hit() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = test_utils::mock_query(&mut server, "15", &test_utils::entry(15))
+            .expect(2)
+            .create_async()
+            .await;
+        let client = CachedFactorDbClient::with_capacity(
+            test_utils::client(&server),
+            NonZeroUsize::new(10).unwrap(),
+        );
+        for _ in 0..3 {
+            client.get(15).await.unwrap();
+        }
+        client.clear_cache();
+        client.get(15).await.unwrap();
+        client.get(15).await.unwrap();
+        mock.assert_async().await;
+    }
+}
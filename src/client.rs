@@ -0,0 +1,1955 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+#[cfg(not(feature = "tracing"))]
+use log::debug;
+use num_bigint::BigInt;
+use reqwest::{Client, Proxy, Response, StatusCode, Url};
+use tokio::sync::{Semaphore, SemaphorePermit};
+#[cfg(feature = "tracing")]
+use tracing::debug;
+
+use crate::factor::factor_local;
+use crate::utils::{body_error, check_status, request_error, validate_endpoint};
+use crate::{FactorDbError, Number, NumberStatus, RetryPolicy, ENDPOINT, USER_AGENT};
+
+/// Number of requests in flight at a time for methods making multiple requests, unless specified otherwise.
+pub(crate) const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Asynchronous API client for factorDB API.
+///
+/// If you need a blocking client, use [`FactorDbBlockingClient`] instead.
+///
+/// If you're making multiple requests, it's probably a good idea to reuse the client to take advantage of keep-alive
+/// connection pooling. ([Learn more](https://docs.rs/reqwest/latest/reqwest/index.html#making-a-get-request))
+///
+/// The client does not spawn any background tasks of its own. Dropping it (and all of its clones) releases the
+/// underlying connection pool, closing any idle connections.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use factordb::FactorDbClient;
+/// use num_bigint::BigInt; // All numeric values in the result object are of this type
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// // Initialise the client
+/// let client = FactorDbClient::new();
+///
+/// // Make requests
+/// let forty_two = client.get(42).await?;
+/// let expect_factors: Vec<BigInt> = vec![2, 3, 7].into_iter().map(|n| BigInt::from(n)).collect();
+/// assert_eq!(forty_two.into_factors_flattened(), expect_factors);
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+///
+/// The client is `Send + Sync`, and cloning it is cheap as clones share the same connection pool, so it can be handed
+/// to spawned tasks:
+///
+/// ```no_run
+/// # use std::error::Error;
+/// use factordb::FactorDbClient;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let client = FactorDbClient::new();
+///
+/// let handles: Vec<_> = (40..45)
+///     .map(|n| {
+///         let client = client.clone();
+///         tokio::spawn(async move { client.get(n).await })
+///     })
+///     .collect();
+/// for handle in handles {
+///     println!("{}", handle.await??);
+/// }
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FactorDbClient {
+    client: Client,
+    endpoint: String,
+    concurrency_limiter: Option<Arc<Semaphore>>,
+    max_response_bytes: Option<usize>,
+    retry_policy: Option<RetryPolicy>,
+    request_count: Arc<AtomicU64>,
+}
+
+impl FactorDbClient {
+    /// Creates a new instance of [`FactorDbClient`] with a default HTTP client, which identifies itself to FactorDB
+    /// with a `User-Agent` of `factordb-rust/<version>`.
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("failed to create HTTP client");
+        Self::with_client(client)
+    }
+
+    /// Creates a [`FactorDbClientBuilder`] to configure a new client.
+    pub fn builder() -> FactorDbClientBuilder {
+        FactorDbClientBuilder::new()
+    }
+
+    /// Creates a new instance of [`FactorDbClient`] with a supplied [`reqwest::Client`].
+    ///
+    /// The `User-Agent` header is left to `client`, so set one with [`reqwest::ClientBuilder::user_agent()`].
+    pub fn with_client(client: Client) -> Self {
+        debug!("Creating async HTTP client");
+        Self {
+            client,
+            endpoint: ENDPOINT.to_string(),
+            concurrency_limiter: None,
+            max_response_bytes: None,
+            retry_policy: None,
+            request_count: Arc::default(),
+        }
+    }
+
+    /// Creates a new instance of [`FactorDbClient`] with a default HTTP client, sending its requests to `endpoint`
+    /// instead of `http://factordb.com/api`.
+    ///
+    /// This can be used to connect over HTTPS (`https://factordb.com/api`) or to a mirror of FactorDB.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::InvalidEndpoint`] if `endpoint` isn't a valid HTTP(S) URL.
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Result<Self, FactorDbError> {
+        Ok(Self {
+            endpoint: validate_endpoint(endpoint.into())?,
+            ..Self::new()
+        })
+    }
+
+    /// Creates a new instance of [`FactorDbClient`] configured by environment variables, all of which are optional:
+    ///
+    /// - `FACTORDB_ENDPOINT` - API endpoint, see [`FactorDbClientBuilder::endpoint()`]
+    /// - `FACTORDB_TIMEOUT_MS` - request timeout in milliseconds, see [`FactorDbClientBuilder::timeout()`]
+    /// - `FACTORDB_USER_AGENT` - `User-Agent` header, see [`FactorDbClientBuilder::user_agent()`]
+    /// - `FACTORDB_PROXY` - proxy URL for all requests, see [`FactorDbClientBuilder::proxy()`]
+    ///
+    /// Without `FACTORDB_PROXY`, the usual `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` variables are
+    /// respected like with any other client.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::InvalidEnvVar`] if a variable isn't valid Unicode or `FACTORDB_TIMEOUT_MS` isn't a
+    /// number, [`FactorDbError::InvalidEndpoint`] if `FACTORDB_ENDPOINT` isn't a valid HTTP(S) URL, or another
+    /// [`FactorDbError`] if the underlying HTTP client can't be built (e.g. because of an invalid proxy URL).
+    pub fn from_env() -> Result<Self, FactorDbError> {
+        Self::from_lookup(|name| std::env::var(name))
+    }
+
+    /// Creates a new instance of [`FactorDbClient`] like [`Self::from_env()`], reading variables through `lookup`.
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Result<String, std::env::VarError>,
+    ) -> Result<Self, FactorDbError> {
+        let env_var = |name: &str| env_var(name, lookup(name));
+        let mut builder = Self::builder();
+        if let Some(endpoint) = env_var("FACTORDB_ENDPOINT")? {
+            builder = builder.endpoint(endpoint);
+        }
+        if let Some(timeout) = env_var("FACTORDB_TIMEOUT_MS")? {
+            let timeout = timeout
+                .parse()
+                .map_err(|_| FactorDbError::InvalidEnvVar("FACTORDB_TIMEOUT_MS".to_string()))?;
+            builder = builder.timeout(Duration::from_millis(timeout));
+        }
+        if let Some(user_agent) = env_var("FACTORDB_USER_AGENT")? {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(proxy) = env_var("FACTORDB_PROXY")? {
+            builder = builder.proxy(proxy);
+        }
+        builder.build()
+    }
+
+    /// Sends a GET request to the FactorDB API for a given number. Returns an instance of [`Factor`].
+    ///
+    /// If the client was built with a [retry policy](FactorDbClientBuilder::retry_policy()), failed requests are
+    /// retried like with [`Self::get_with_retry()`].
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub async fn get<T: Display>(&self, number: T) -> Result<Number, FactorDbError> {
+        if let Some(policy) = self.retry_policy {
+            return self.get_with_retry(number, policy).await;
+        }
+        let (number, _) = self.get_with_status(number).await?;
+        Ok(number)
+    }
+
+    /// Same as [`Self::get()`], but takes the number as ASCII decimal digits, e.g. `b"1234"`.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::InvalidNumber`] if `digits` is empty or contains anything other than ASCII digits.
+    /// Otherwise, returns a [`FactorDbError`] in the same cases as [`Self::get()`].
+    pub async fn get_bytes(&self, digits: &[u8]) -> Result<Number, FactorDbError> {
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Err(FactorDbError::InvalidNumber);
+        }
+        let digits = std::str::from_utf8(digits).expect("ASCII digits are valid UTF-8");
+        self.get(digits).await
+    }
+
+    /// Same as [`Self::get()`], but also returns the HTTP status code FactorDB responded with.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub async fn get_with_status<T: Display>(
+        &self,
+        number: T,
+    ) -> Result<(Number, StatusCode), FactorDbError> {
+        self.fetch_number(number, None).await
+    }
+
+    /// Same as [`Self::get()`], but returns `None` if the number isn't in the database (i.e. its status is
+    /// [`NumberStatus::NotInDatabase`]), so that "not found" can be told apart from failed requests.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub async fn try_get<T: Display>(&self, number: T) -> Result<Option<Number>, FactorDbError> {
+        let number = self.get(number).await?;
+        Ok((number.status() != &NumberStatus::NotInDatabase).then_some(number))
+    }
+
+    /// Same as [`Self::get()`], but only returns numbers whose factorization is complete and proven, i.e. which are
+    /// fully factored, proven prime or 1.
+    ///
+    /// This saves pipelines which must not proceed on partial data from checking [`Number::status()`] everywhere.
+    /// Probable primes count as not fully factored, as their primality hasn't been proven.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::NotFullyFactored`] with the partial result if the number isn't fully factored, or
+    /// another [`FactorDbError`] if the request fails.
+    pub async fn get_fully_factored<T: Display>(&self, number: T) -> Result<Number, FactorDbError> {
+        let number = self.get(number).await?;
+        match number.status() {
+            NumberStatus::FullyFactored | NumberStatus::DefinitelyPrime | NumberStatus::Unit => {
+                Ok(number)
+            }
+            _ => Err(FactorDbError::NotFullyFactored(Box::new(number))),
+        }
+    }
+
+    /// Same as [`Self::get()`], but gives up if the request doesn't complete within `timeout`.
+    ///
+    /// This overrides the timeout the client was built with (if any) for this request only.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::Timeout`] if the request times out, or another [`FactorDbError`] if either the API
+    /// request responded with an error or there is an error in the request or parsing of the response.
+    pub async fn get_with_timeout<T: Display>(
+        &self,
+        number: T,
+        timeout: Duration,
+    ) -> Result<Number, FactorDbError> {
+        let (number, _) = self.fetch_number(number, Some(timeout)).await?;
+        Ok(number)
+    }
+
+    /// Fetches and parses the entry for `number`, optionally with a timeout for this request.
+    async fn fetch_number<T: Display>(
+        &self,
+        number: T,
+        timeout: Option<Duration>,
+    ) -> Result<(Number, StatusCode), FactorDbError> {
+        let query = number.to_string();
+        let _permit = self.acquire_permit().await;
+        record(async {
+            let response = self.fetch_response(&query, timeout).await?;
+            let status = response.status();
+            // reqwest follows redirects, so the final URL tells whether FactorDB resolved the query to another one. Only
+            // the query itself is compared, as a redirect may also change e.g. the scheme or host.
+            let sent_query = Url::parse(&self.query_url(&query))
+                .ok()
+                .and_then(|url| query_param(&url));
+            let redirected_query = query_param(response.url())
+                .filter(|redirected| Some(redirected) != sent_query.as_ref());
+            let mut number = self.parse_number(response).await?;
+            if let Some(redirected_query) = redirected_query {
+                number.set_canonical_query(redirected_query);
+            }
+            Ok((number, status))
+        })
+        .await
+    }
+
+    /// Looks up the FactorDB entry with the given ID, as returned by [`Number::id()`].
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub async fn get_by_id<T: Display>(&self, id: T) -> Result<Number, FactorDbError> {
+        let _permit = self.acquire_permit().await;
+        record(async {
+            let response = self
+                .send(format!("{}?id={}", self.endpoint, id), None)
+                .await?;
+            self.parse_number(response).await
+        })
+        .await
+    }
+
+    /// Looks up the FactorDB entries with the given IDs, e.g. to mirror a range of the database.
+    ///
+    /// Up to 8 requests are in flight at a time. The results are in the same order as `ids`, and a failed request
+    /// doesn't stop the other ones.
+    pub async fn get_ids(
+        &self,
+        ids: impl IntoIterator<Item = BigInt>,
+    ) -> Vec<Result<Number, FactorDbError>> {
+        stream::iter(ids)
+            .map(|id| self.get_by_id(id))
+            .buffered(DEFAULT_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Sends a GET request to the FactorDB API for a given number and returns its JSON response.
+    ///
+    /// The body is always decoded as UTF-8 like JSON requires, regardless of the `charset` of the response, with
+    /// invalid sequences replaced by `U+FFFD`.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if either the API request responded with an error or there is an error in the
+    /// request or parsing of the response.
+    pub async fn get_json<T: Display>(&self, number: T) -> Result<String, FactorDbError> {
+        let _permit = self.acquire_permit().await;
+        record(async {
+            let response = self.fetch_response(number, None).await?;
+            let body = self.read_body(response).await?;
+            Ok(String::from_utf8_lossy(&body).into_owned())
+        })
+        .await
+    }
+
+    /// Checks whether `n` is colossally abundant for the given `epsilon`, i.e. whether `sigma(n) / n^(1 + epsilon)`
+    /// is at least as large as that of every smaller positive integer.
+    ///
+    /// This is a bounded, heuristic check: a colossally abundant number needs to beat *every* other positive integer
+    /// for some `epsilon`, but only the numbers below `n` are compared here, and only for the supplied `epsilon`.
+    /// `n` itself and every number below it are fetched from FactorDB, so this makes up to `n` requests (up to 8 at a
+    /// time, see [`Self::is_colossally_abundant_with_concurrency()`]), stopping at the first number that beats `n`.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests fails, or [`FactorDbError::Overflow`] if FactorDB returns a
+    /// factor whose exponent doesn't fit in a [`u32`].
+    pub async fn is_colossally_abundant(
+        &self,
+        n: u64,
+        epsilon: f64,
+    ) -> Result<bool, FactorDbError> {
+        self.is_colossally_abundant_with_concurrency(n, epsilon, DEFAULT_CONCURRENCY)
+            .await
+    }
+
+    /// Same as [`Self::is_colossally_abundant()`], but with up to `limit` requests in flight at a time.
+    pub async fn is_colossally_abundant_with_concurrency(
+        &self,
+        n: u64,
+        epsilon: f64,
+        limit: usize,
+    ) -> Result<bool, FactorDbError> {
+        if n == 0 {
+            return Ok(false);
+        }
+        let score = self.get(n).await?.colossal_abundance_score(epsilon)?;
+        let mut smaller = stream::iter(1..n)
+            .map(|k| self.get(k))
+            .buffered(limit.max(1));
+        while let Some(number) = smaller.next().await {
+            if number?.colossal_abundance_score(epsilon)? > score {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Fetches the factorization of [`Number::euler_totient()`] of `number` from FactorDB, e.g. as the order of the
+    /// multiplicative group modulo `n` when computing multiplicative orders or primitive roots.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if the request fails.
+    pub async fn totient_factorization(&self, number: &Number) -> Result<Number, FactorDbError> {
+        self.get(number.euler_totient()).await
+    }
+
+    /// Computes the iterated totient chain of `number`, i.e. `n, φ(n), φ(φ(n)), ...` down to 1.
+    ///
+    /// Every value in the chain is looked up in FactorDB one at a time, since each totient depends on the factorization
+    /// of the previous value. The chain of `n` is roughly `log2(n)` steps long, so this makes about as many requests.
+    /// The totients are computed with [`Number::euler_totient()`], so they are only correct as long as FactorDB has
+    /// fully factored every value in the chain.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests fails.
+    pub async fn totient_chain<T: Display>(&self, number: T) -> Result<Vec<BigInt>, FactorDbError> {
+        let one = BigInt::from(1);
+        let mut number = self.get(number).await?;
+        let mut chain = vec![number.value()];
+        while chain[chain.len() - 1] > one {
+            let totient = number.euler_totient();
+            number = self.get(&totient).await?;
+            chain.push(totient);
+        }
+        Ok(chain)
+    }
+
+    /// Computes the aliquot sequence of `number`, i.e. `n, s(n), s(s(n)), ...` where `s(n)` is the sum of the proper
+    /// divisors of `n`, for up to `max_steps` steps.
+    ///
+    /// The sequence ends early once it reaches 1 (or 0), or once a value repeats, in which case the repeated value is
+    /// the last one in the sequence. That way perfect numbers end up as `[6, 6]`, amicable pairs as
+    /// `[220, 284, 220]` and sociable numbers in longer cycles. Every value in the sequence is looked up in FactorDB
+    /// one at a time, so this makes up to `max_steps` requests (but at least one). Like [`Self::totient_chain()`],
+    /// the result is only correct as long as FactorDB has fully factored every value.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests fails.
+    pub async fn aliquot_sequence<T: Display>(
+        &self,
+        number: T,
+        max_steps: usize,
+    ) -> Result<Vec<BigInt>, FactorDbError> {
+        let one = BigInt::from(1);
+        let first = self.get(number).await?;
+        let mut sequence = vec![first.value()];
+        let mut fetched = Some(first);
+        for _ in 0..max_steps {
+            let current = &sequence[sequence.len() - 1];
+            if current <= &one {
+                break;
+            }
+            let number = match fetched.take() {
+                Some(number) => number,
+                None => self.get(current).await?,
+            };
+            let next = number.sigma() - current;
+            let repeated = sequence.contains(&next);
+            sequence.push(next);
+            if repeated {
+                break;
+            }
+        }
+        Ok(sequence)
+    }
+
+    /// Factors `n` locally with [`factor_local()`] and checks whether FactorDB agrees with the result.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if the request fails.
+    pub async fn verify_local(&self, n: u64) -> Result<bool, FactorDbError> {
+        let number = self.get(n).await?;
+        Ok(agrees_with_local(&number, n))
+    }
+
+    /// Runs [`Self::verify_local()`] on `count` consecutive integers starting from `start`, returning each integer
+    /// along with whether FactorDB agrees with the local factorization.
+    ///
+    /// This is meant for auditing the data in FactorDB. Up to 8 requests are in flight at a time.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests fails.
+    pub async fn audit_range(
+        &self,
+        start: u64,
+        count: u64,
+    ) -> Result<Vec<(BigInt, bool)>, FactorDbError> {
+        let range = start..start.saturating_add(count);
+        let numbers = self.get_many(range.clone()).await;
+        range
+            .zip(numbers)
+            .map(|(n, number)| Ok((BigInt::from(n), agrees_with_local(&number?, n))))
+            .collect()
+    }
+
+    /// Fetches the given numbers concurrently and returns the ones whose [`Number`] satisfies `predicate`, in the
+    /// same order as the input.
+    ///
+    /// The numbers are returned as they were passed in (e.g. `2^127-1` stays an expression), rather than as the value
+    /// FactorDB resolved them to. Up to 8 requests are in flight at a time.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests fails.
+    pub async fn filter_numbers<T: Display>(
+        &self,
+        numbers: impl IntoIterator<Item = T>,
+        predicate: impl Fn(&Number) -> bool,
+    ) -> Result<Vec<T>, FactorDbError> {
+        let numbers: Vec<T> = numbers.into_iter().collect();
+        let results = self.get_many(&numbers).await;
+        let mut matching = vec![];
+        for (input, result) in numbers.into_iter().zip(results) {
+            if predicate(&result?) {
+                matching.push(input);
+            }
+        }
+        Ok(matching)
+    }
+
+    /// Returns the first of `candidates` which FactorDB hasn't fully factored yet, i.e. whose status is
+    /// [`NumberStatus::NoFactorsKnown`] or [`NumberStatus::FactorsKnown`], or `None` if all of them are.
+    ///
+    /// FactorDB's API has no query for the smallest unfactored number in the database, so this scans the given
+    /// candidates instead: pass them in ascending order (e.g. a range, or the terms of a sequence) to find the
+    /// smallest unfactored one among them. Up to 8 requests are in flight at a time, and no more candidates are
+    /// requested once one is found.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests before the unfactored number fails.
+    pub async fn smallest_unfactored<T: Display>(
+        &self,
+        candidates: impl IntoIterator<Item = T>,
+    ) -> Result<Option<Number>, FactorDbError> {
+        let mut numbers = stream::iter(candidates)
+            .map(|n| self.get(n))
+            .buffered(DEFAULT_CONCURRENCY);
+        while let Some(number) = numbers.next().await {
+            let number = number?;
+            if matches!(
+                number.status(),
+                NumberStatus::NoFactorsKnown | NumberStatus::FactorsKnown
+            ) {
+                return Ok(Some(number));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Fetches all of `numbers` concurrently, returning the results in the same order as the input.
+    ///
+    /// Up to 8 requests are in flight at a time; use [`Self::get_many_with_concurrency()`] to change that. A failed
+    /// request doesn't stop the other ones, see [`Self::try_get_many()`] for that.
+    pub async fn get_many<T: Display>(
+        &self,
+        numbers: impl IntoIterator<Item = T>,
+    ) -> Vec<Result<Number, FactorDbError>> {
+        self.get_many_with_concurrency(numbers, DEFAULT_CONCURRENCY)
+            .await
+    }
+
+    /// Same as [`Self::get_many()`], but with up to `limit` requests in flight at a time.
+    pub async fn get_many_with_concurrency<T: Display>(
+        &self,
+        numbers: impl IntoIterator<Item = T>,
+        limit: usize,
+    ) -> Vec<Result<Number, FactorDbError>> {
+        stream::iter(numbers)
+            .map(|n| self.get(n))
+            .buffered(limit.max(1))
+            .collect()
+            .await
+    }
+
+    /// Fetches all of `numbers` concurrently, returning them in the same order as the input, or the first error.
+    ///
+    /// Up to 8 requests are in flight at a time. As soon as a request fails, the requests still in flight are
+    /// cancelled and the remaining numbers are never requested.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests fails.
+    pub async fn try_get_many<T: Display>(
+        &self,
+        numbers: impl IntoIterator<Item = T>,
+    ) -> Result<Vec<Number>, FactorDbError> {
+        stream::iter(numbers)
+            .map(|n| self.get(n))
+            .buffered(DEFAULT_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Same as [`Self::get()`], but retries the request according to `policy` if it fails for a transient reason, e.g.
+    /// because FactorDB is overloaded.
+    ///
+    /// Terminal errors such as [`FactorDbError::InvalidNumber`] are not retried. See [`RetryPolicy`] for which errors
+    /// are retried and how long to wait in between.
+    ///
+    /// # Errors
+    /// Returns the last [`FactorDbError`] once `policy.max_retries` retries are exhausted, or the first error which
+    /// isn't worth retrying.
+    pub async fn get_with_retry<T: Display>(
+        &self,
+        number: T,
+        policy: RetryPolicy,
+    ) -> Result<Number, FactorDbError> {
+        let query = number.to_string();
+        let mut retries = 0;
+        loop {
+            match self.get_with_status(&query).await.map(|(number, _)| number) {
+                Err(e) if retries < policy.max_retries && e.is_retryable() => {
+                    let delay = policy.delay(retries, &e);
+                    debug!("Retrying {} in {:?} after error: {}", query, delay, e);
+                    tokio::time::sleep(delay).await;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Fetches `number` along with the FactorDB entries of each of its distinct factors, e.g. to confirm their primality
+    /// or look up their IDs.
+    ///
+    /// The factor entries are in the same order as [`Number::unique_factors()`], leaving out the trivial factors of 0
+    /// and 1. Once `number` itself is fetched, up to 8 requests for its factors are in flight at a time.
+    ///
+    /// # Errors
+    /// Returns a [`FactorDbError`] if any of the requests fails.
+    pub async fn get_with_factor_entries<T: Display>(
+        &self,
+        number: T,
+    ) -> Result<(Number, Vec<Number>), FactorDbError> {
+        let number = self.get(number).await?;
+        let one = BigInt::from(1);
+        let factors = number.unique_factors().into_iter().filter(|f| *f > &one);
+        let entries = self
+            .get_many(factors)
+            .await
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+        Ok((number, entries))
+    }
+
+    /// Polls FactorDB for a given number every `interval`, yielding a new [`Number`] snapshot each time its status or
+    /// factors change.
+    ///
+    /// The first snapshot is yielded straight away. The stream ends once the factorization can't progress any
+    /// further, i.e. when the number is fully factored, (probably) prime, or is 0 or 1, or after yielding the first
+    /// error.
+    pub fn watch<T: Display>(
+        &self,
+        number: T,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<Number, FactorDbError>> + '_ {
+        let query = number.to_string();
+        // the state is `None` once the stream has ended, otherwise it holds the last yielded snapshot (if any)
+        stream::unfold(Some(None), move |previous: Option<Option<Number>>| {
+            let query = query.clone();
+            async move {
+                let mut previous = previous?;
+                loop {
+                    if previous.is_some() {
+                        tokio::time::sleep(interval).await;
+                    }
+                    match self.get(&query).await {
+                        Ok(number) if previous.as_ref() == Some(&number) => continue,
+                        Ok(number) => {
+                            let next = if number.is_factorization_complete() {
+                                None
+                            } else {
+                                previous = Some(number.clone());
+                                Some(previous)
+                            };
+                            return Some((Ok(number), next));
+                        }
+                        Err(e) => return Some((Err(e), None)),
+                    }
+                }
+            }
+        })
+    }
+
+    /// Returns how many requests this client (including all of its clones) has sent to FactorDB so far, e.g. to keep
+    /// track of an API call budget.
+    ///
+    /// Every request counts, whether it succeeded or not, while results served from elsewhere (such as the cache of a
+    /// [`CachedFactorDbClient`](crate::CachedFactorDbClient)) don't.
+    pub fn request_count(&self) -> u64 {
+        self.request_count.load(Ordering::Relaxed)
+    }
+
+    /// Make the actual web request, optionally overriding the client's timeout
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(query = %number, status), err(level = "debug"))
+    )]
+    async fn fetch_response<T: Display>(
+        &self,
+        number: T,
+        timeout: Option<Duration>,
+    ) -> Result<Response, FactorDbError> {
+        let response = self.send(self.query_url(number), timeout).await?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("status", response.status().as_u16());
+        Ok(response)
+    }
+
+    /// Acquires a permit from the concurrency limiter, if any. Hold it until the response has been read, so that the
+    /// limiter covers the whole request rather than just waiting for the headers.
+    async fn acquire_permit(&self) -> Option<SemaphorePermit<'_>> {
+        // a closed semaphore can't limit anything anymore, so just carry on without a permit
+        self.concurrency_limiter.as_ref()?.acquire().await.ok()
+    }
+
+    /// Sends a request to `url`, turning unsuccessful responses into errors.
+    async fn send(
+        &self,
+        url: String,
+        timeout: Option<Duration>,
+    ) -> Result<Response, FactorDbError> {
+        debug!("Fetching API response from {}", url);
+        self.request_count.fetch_add(1, Ordering::Relaxed);
+        let mut request = self.client.get(url);
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send().await.map_err(request_error)?;
+        check_status(response.status(), response.headers())?;
+        Ok(response)
+    }
+
+    /// Parses the body of `response` as a [`Number`].
+    async fn parse_number(&self, response: Response) -> Result<Number, FactorDbError> {
+        let body = self.read_body(response).await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Reads the body of `response`, giving up as soon as it exceeds the configured maximum size.
+    async fn read_body(&self, mut response: Response) -> Result<Vec<u8>, FactorDbError> {
+        let Some(limit) = self.max_response_bytes else {
+            let body = response.bytes().await.map_err(body_error)?;
+            return Ok(body.into());
+        };
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(FactorDbError::ResponseTooLarge(limit));
+        }
+        let mut body = vec![];
+        while let Some(chunk) = response.chunk().await.map_err(body_error)? {
+            if body.len() + chunk.len() > limit {
+                return Err(FactorDbError::ResponseTooLarge(limit));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    /// The URL of the API request for `number`, normalized the same way as the URL of a response.
+    fn query_url<T: Display>(&self, number: T) -> String {
+        let url = format!("{}?query={}", self.endpoint, number);
+        Url::parse(&url).map_or(url, String::from)
+    }
+}
+
+impl Default for FactorDbClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the decoded `query` parameter of `url`.
+fn query_param(url: &Url) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == "query")
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Checks the `value` of the environment variable `name`, treating an empty value the same as an unset one.
+fn env_var(
+    name: &str,
+    value: Result<String, std::env::VarError>,
+) -> Result<Option<String>, FactorDbError> {
+    match value {
+        Ok(value) if value.is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => {
+            Err(FactorDbError::InvalidEnvVar(name.to_string()))
+        }
+    }
+}
+
+/// Runs `request`, which sends a request and reads its response, recording it in the metrics if enabled.
+///
+/// This covers reading and parsing the body as well, so that errors there are counted too.
+async fn record<R>(
+    request: impl std::future::Future<Output = Result<R, FactorDbError>>,
+) -> Result<R, FactorDbError> {
+    #[cfg(feature = "metrics")]
+    let start = std::time::Instant::now();
+    let result = request.await;
+    #[cfg(feature = "metrics")]
+    crate::telemetry::record_request(start.elapsed(), result.as_ref().err());
+    result
+}
+
+/// Checks whether the factors FactorDB returned for `n` match [`factor_local()`].
+fn agrees_with_local(number: &Number, n: u64) -> bool {
+    let mut remote = number.factors().clone();
+    remote.sort_unstable();
+    remote == factor_local(n)
+}
+
+/// A builder to configure a [`FactorDbClient`].
+///
+/// Every option is optional, so [`FactorDbClient::new()`] remains the way to get a client with the default
+/// configuration.
+///
+/// # Examples
+///
+/// ```
+/// # use std::error::Error;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use factordb::{FactorDbClient, RetryPolicy};
+/// use tokio::sync::Semaphore;
+///
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// // shared with the rest of the application, which can have at most 10 outgoing requests at a time
+/// let semaphore = Arc::new(Semaphore::new(10));
+///
+/// let client = FactorDbClient::builder()
+///     .endpoint("https://factordb.com/api")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .retry_policy(RetryPolicy::default())
+///     .concurrency_limiter(semaphore.clone())
+///     .max_response_bytes(1 << 20)
+///     .build()?;
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct FactorDbClientBuilder {
+    endpoint: Option<String>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxy: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    concurrency_limiter: Option<Arc<Semaphore>>,
+    max_response_bytes: Option<usize>,
+    no_keepalive: bool,
+}
+
+impl FactorDbClientBuilder {
+    /// Creates a new builder with the default configuration, equivalent to [`FactorDbClient::new()`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends requests to `endpoint` instead of `http://factordb.com/api`, see [`FactorDbClient::with_endpoint()`].
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Gives up on requests which haven't completed within `timeout`, returning [`FactorDbError::Timeout`]. By default,
+    /// requests never time out.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends `user_agent` as the `User-Agent` header of every request instead of `factordb-rust/<version>`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sends all requests through the proxy at `url`, e.g. `http://localhost:8080` or `socks5://localhost:1080`.
+    pub fn proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Makes [`FactorDbClient::get()`] and the methods built on it retry failed requests according to `policy`. By
+    /// default, requests aren't retried.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Makes the client acquire a permit from `semaphore` before sending each request, holding it until the response
+    /// has been read.
+    ///
+    /// This lets the client share a concurrency budget with the rest of the application, rather than only limiting
+    /// the requests it makes itself. If the semaphore is closed, requests are sent without a permit.
+    pub fn concurrency_limiter(mut self, semaphore: Arc<Semaphore>) -> Self {
+        self.concurrency_limiter = Some(semaphore);
+        self
+    }
+
+    /// Rejects responses with a body larger than `bytes` with [`FactorDbError::ResponseTooLarge`], without reading
+    /// more than that into memory. By default, responses of any size are accepted.
+    ///
+    /// FactorDB's own responses are small, so this is mostly useful to guard against untrusted mirrors.
+    pub fn max_response_bytes(mut self, bytes: usize) -> Self {
+        self.max_response_bytes = Some(bytes);
+        self
+    }
+
+    /// Closes connections as soon as their requests are done instead of keeping them alive for later requests.
+    ///
+    /// Reusing connections only pays off for clients making many requests, so short-lived processes which look up a
+    /// single number (e.g. command line tools) can use this to avoid idle connections delaying their exit.
+    pub fn no_keepalive(mut self) -> Self {
+        self.no_keepalive = true;
+        self
+    }
+
+    /// Builds the [`FactorDbClient`].
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::InvalidEndpoint`] if the endpoint isn't a valid HTTP(S) URL, or another
+    /// [`FactorDbError`] if the underlying HTTP client can't be built (e.g. because of an invalid proxy URL).
+    pub fn build(self) -> Result<FactorDbClient, FactorDbError> {
+        let endpoint = match self.endpoint {
+            Some(endpoint) => validate_endpoint(endpoint)?,
+            None => ENDPOINT.to_string(),
+        };
+        let mut builder = Client::builder();
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder = match self.user_agent {
+            Some(user_agent) => builder.user_agent(user_agent),
+            None => builder.user_agent(USER_AGENT),
+        };
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?);
+        }
+        if self.no_keepalive {
+            builder = builder.pool_max_idle_per_host(0);
+        }
+        let client = builder.build()?;
+        debug!("Creating async HTTP client");
+        Ok(FactorDbClient {
+            client,
+            endpoint,
+            concurrency_limiter: self.concurrency_limiter,
+            max_response_bytes: self.max_response_bytes,
+            retry_policy: self.retry_policy,
+            request_count: Arc::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use mockito::Matcher;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_two_factors() {
+        let client = FactorDbClient::new();
+        let result = client.get(15).await.unwrap();
+        assert_eq!(
+            vec![BigInt::from(3), BigInt::from(5)],
+            result.into_factors_flattened()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_repeating_factors() {
+        let client = FactorDbClient::new();
+        let result = client.get(100).await.unwrap();
+        assert_eq!(
+            vec![
+                BigInt::from(2),
+                BigInt::from(2),
+                BigInt::from(5),
+                BigInt::from(5)
+            ],
+            result.clone().into_factors_flattened()
+        );
+        assert_eq!(
+            vec![BigInt::from(2), BigInt::from(5)],
+            result.into_unique_factors()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prime() {
+        let client = FactorDbClient::new();
+        let result = client.get(17).await.unwrap();
+        let flatenned = result.clone().into_factors_flattened();
+        let unique = result.into_unique_factors();
+        assert_eq!(vec![BigInt::from(17)], flatenned);
+        assert_eq!(flatenned, unique);
+    }
+
+    #[tokio::test]
+    async fn test_invalid() {
+        let client = FactorDbClient::new();
+        let result = client.get("AAAAA").await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_colossally_abundant() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=12).await;
+        let client = test_utils::client(&server);
+        assert!(client.is_colossally_abundant(12, 0.1).await.unwrap());
+        assert!(!client.is_colossally_abundant(10, 0.1).await.unwrap());
+        assert!(client
+            .is_colossally_abundant_with_concurrency(12, 0.1, 1)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_with_endpoint() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let client = FactorDbClient::with_endpoint(format!("{}/api", server.url())).unwrap();
+        assert!(client.get(15).await.is_ok());
+        assert!(FactorDbClient::with_endpoint("https://factordb.com/api").is_ok());
+        for endpoint in ["factordb.com/api", "ftp://factordb.com/api", ""] {
+            assert!(matches!(
+                FactorDbClient::with_endpoint(endpoint),
+                Err(FactorDbError::InvalidEndpoint(e)) if e == endpoint
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses() {
+        let mut server = mockito::Server::new_async().await;
+        for (query, status) in [
+            ("400", 400),
+            ("404", 404),
+            ("429", 429),
+            ("500", 500),
+            ("503", 503),
+        ] {
+            test_utils::mock_query(&mut server, query, "")
+                .with_status(status)
+                .create_async()
+                .await;
+        }
+        test_utils::mock_query(&mut server, "retry", "")
+            .with_status(429)
+            .with_header("retry-after", "30")
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        for query in ["400", "404"] {
+            assert!(matches!(
+                client.get(query).await,
+                Err(FactorDbError::InvalidNumber)
+            ));
+        }
+        assert!(matches!(
+            client.get("429").await,
+            Err(FactorDbError::RateLimited { retry_after: None })
+        ));
+        assert!(matches!(
+            client.get_json("retry").await,
+            Err(FactorDbError::RateLimited {
+                retry_after: Some(30)
+            })
+        ));
+        assert!(matches!(
+            client.get("500").await,
+            Err(FactorDbError::ServerError(
+                StatusCode::INTERNAL_SERVER_ERROR
+            ))
+        ));
+        assert!(matches!(
+            client.get_json("503").await,
+            Err(FactorDbError::ServerError(StatusCode::SERVICE_UNAVAILABLE))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_with_retry() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = test_utils::mock_query(&mut server, "15", "")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let rate_limited = test_utils::mock_query(&mut server, "15", "")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create_async()
+            .await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let invalid = test_utils::mock_query(&mut server, "404", "")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        let policy = RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+        };
+        assert_eq!(
+            client.get_with_retry(15, policy).await.unwrap().value(),
+            BigInt::from(15)
+        );
+        unavailable.assert_async().await;
+        rate_limited.assert_async().await;
+        assert!(matches!(
+            client.get_with_retry(404, policy).await,
+            Err(FactorDbError::InvalidNumber)
+        ));
+        invalid.assert_async().await;
+
+        let unavailable = test_utils::mock_query(&mut server, "16", "")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let policy = RetryPolicy {
+            max_retries: 1,
+            ..policy
+        };
+        assert!(matches!(
+            client.get_with_retry(16, policy).await,
+            Err(FactorDbError::ServerError(StatusCode::SERVICE_UNAVAILABLE))
+        ));
+        unavailable.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_error_classification() {
+        let mut server = mockito::Server::new_async().await;
+        for (query, status) in [("404", 404), ("429", 429), ("503", 503), ("300", 300)] {
+            test_utils::mock_query(&mut server, query, "")
+                .with_status(status)
+                .create_async()
+                .await;
+        }
+        test_utils::mock_query(&mut server, "html", "<html></html>")
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        for (query, retryable, client_error) in [
+            ("404", false, true),
+            ("429", true, true),
+            ("503", true, false),
+            ("300", false, false),
+            ("html", false, false),
+        ] {
+            let error = client.get(query).await.unwrap_err();
+            assert_eq!(error.is_retryable(), retryable, "{}", error);
+            assert_eq!(error.is_client_error(), client_error, "{}", error);
+        }
+        assert!(matches!(
+            client.get(300).await,
+            Err(FactorDbError::UnexpectedStatus(
+                StatusCode::MULTIPLE_CHOICES
+            ))
+        ));
+        // nothing listens on port 1, so connecting fails
+        let client = FactorDbClient::with_endpoint("http://127.0.0.1:1/api").unwrap();
+        let error = client.get(15).await.unwrap_err();
+        assert!(error.is_retryable(), "{}", error);
+        assert!(!error.is_client_error());
+    }
+
+    #[tokio::test]
+    async fn test_get_with_timeout() {
+        // accepts connections (through the backlog) but never responds
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/api", listener.local_addr().unwrap());
+        let client = FactorDbClient::with_endpoint(endpoint).unwrap();
+        let error = client
+            .get_with_timeout(15, Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, FactorDbError::Timeout));
+        assert!(error.is_retryable());
+
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let client = test_utils::client(&server);
+        assert!(client
+            .get_with_timeout(15, Duration::from_secs(10))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_request_count() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=10).await;
+        let client = test_utils::client(&server);
+        assert_eq!(client.request_count(), 0);
+        client.get(1).await.unwrap();
+        assert!(client.get("bad").await.is_err());
+        let clone = client.clone();
+        clone.get_many(1..=8).await;
+        assert_eq!(client.request_count(), 10);
+        assert_eq!(clone.request_count(), 10);
+        assert_eq!(test_utils::client(&server).request_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_try_get() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        test_utils::mock_query(
+            &mut server,
+            "missing",
+            r#"{"id":"0","status":"N","factors":[]}"#,
+        )
+        .create_async()
+        .await;
+        test_utils::mock_query(&mut server, "404", "")
+            .with_status(404)
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        assert!(client.try_get(15).await.unwrap().is_some());
+        assert!(client.try_get("missing").await.unwrap().is_none());
+        assert!(client.try_get(404).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_fully_factored() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=15).await;
+        test_utils::mock_query(
+            &mut server,
+            "partial",
+            r#"{"id":"1100000000512376843","status":"CF","factors":[["3",1],["1000000016000000063",1]]}"#,
+        )
+        .create_async()
+        .await;
+        let client = test_utils::client(&server);
+        for n in [1, 13, 15] {
+            assert_eq!(
+                client.get_fully_factored(n).await.unwrap().value(),
+                BigInt::from(n)
+            );
+        }
+        match client.get_fully_factored("partial").await {
+            Err(FactorDbError::NotFullyFactored(number)) => {
+                assert_eq!(number.status(), &NumberStatus::FactorsKnown);
+                assert_eq!(number.factors().len(), 2);
+            }
+            result => panic!("expected NotFullyFactored, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_response() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(Matcher::Any)
+            .with_header("content-type", "text/html")
+            .with_body("<html><body>Something went wrong</body></html>")
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        assert!(matches!(
+            client.get(15).await,
+            Err(FactorDbError::ParseError(_))
+        ));
+        assert!(client.get_json(15).await.unwrap().starts_with("<html>"));
+    }
+
+    #[tokio::test]
+    async fn test_value() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 100..=100).await;
+        let client = test_utils::client(&server);
+        assert_eq!(client.get(100).await.unwrap().value(), BigInt::from(100));
+    }
+
+    #[tokio::test]
+    async fn test_get_with_status() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_query(&mut server, "15", &test_utils::entry(15))
+            .with_status(203)
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        let (number, status) = client.get_with_status(15).await.unwrap();
+        assert_eq!(status, StatusCode::NON_AUTHORITATIVE_INFORMATION);
+        assert_eq!(
+            number.into_factors_flattened(),
+            vec![BigInt::from(3), BigInt::from(5)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_probably_prime() {
+        let mut server = mockito::Server::new_async().await;
+        let prp = "170141183460469231731687303715884105727";
+        test_utils::mock_query(
+            &mut server,
+            prp,
+            &format!(
+                r#"{{"id":"1100000000000000017","status":"PRP","factors":[["{}",1]]}}"#,
+                prp
+            ),
+        )
+        .create_async()
+        .await;
+        let client = test_utils::client(&server);
+        let number = client.get(prp).await.unwrap();
+        assert_eq!(number.status(), &NumberStatus::ProbablyPrime);
+        assert!(number.is_prime());
+        assert!(!number.is_definitely_prime());
+    }
+
+    #[tokio::test]
+    async fn test_canonical_query() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(Matcher::UrlEncoded("query".into(), "2^10".into()))
+            .with_status(302)
+            .with_header("location", "/api?query=1024")
+            .create_async()
+            .await;
+        test_utils::mock_query(&mut server, "1024", &test_utils::entry(1024))
+            .create_async()
+            .await;
+        test_utils::mock_query(&mut server, "2+3", &test_utils::entry(5))
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        let number = client.get("2^10").await.unwrap();
+        assert_eq!(number.canonical_query(), Some("1024"));
+        assert_eq!(number.into_factors_flattened(), vec![BigInt::from(2); 10]);
+        assert_eq!(client.get(1024).await.unwrap().canonical_query(), None);
+        assert_eq!(client.get("2%2B3").await.unwrap().canonical_query(), None);
+        // a redirect that keeps the query, like one from http to https, doesn't canonicalize it
+        server
+            .mock("GET", "/api")
+            .match_query(Matcher::UrlEncoded("query".into(), "15".into()))
+            .with_status(301)
+            .with_header("location", "/v2/api?query=15")
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/v2/api")
+            .match_query(Matcher::UrlEncoded("query".into(), "15".into()))
+            .with_body(test_utils::entry(15))
+            .create_async()
+            .await;
+        let number = client.get(15).await.unwrap();
+        assert_eq!(number.value(), BigInt::from(15));
+        assert_eq!(number.canonical_query(), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_ids() {
+        let mut server = mockito::Server::new_async().await;
+        for (id, body) in [
+            (
+                "1100000000000000012",
+                r#"{"id":"1100000000000000012","status":"FF","factors":[["2",2],["3",1]]}"#,
+            ),
+            (
+                "1100000000000000017",
+                r#"{"id":"1100000000000000017","status":"P","factors":[["17",1]]}"#,
+            ),
+        ] {
+            server
+                .mock("GET", "/api")
+                .match_query(Matcher::UrlEncoded("id".into(), id.into()))
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .create_async()
+                .await;
+        }
+        let client = test_utils::client(&server);
+        let ids = [
+            "1100000000000000012",
+            "1100000000000000017",
+            "1100000000000000000",
+        ]
+        .map(|id| id.parse::<BigInt>().unwrap());
+        let numbers = client.get_ids(ids.clone()).await;
+        assert_eq!(numbers.len(), 3);
+        assert_eq!(numbers[0].as_ref().unwrap().id(), &ids[0]);
+        assert_eq!(
+            numbers[0]
+                .as_ref()
+                .unwrap()
+                .clone()
+                .into_factors_flattened(),
+            [2, 2, 3].map(BigInt::from)
+        );
+        assert_eq!(numbers[1].as_ref().unwrap().id(), &ids[1]);
+        assert!(numbers[1].as_ref().unwrap().is_definitely_prime());
+        assert!(numbers[2].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_bytes() {
+        let mut server = mockito::Server::new_async().await;
+        let digits = [b'1'; 100];
+        let query = std::str::from_utf8(&digits).unwrap();
+        test_utils::mock_query(
+            &mut server,
+            query,
+            &format!(
+                r#"{{"id":"1100000000000000100","status":"C","factors":[["{}",1]]}}"#,
+                query
+            ),
+        )
+        .create_async()
+        .await;
+        let client = test_utils::client(&server);
+        let number = client.get_bytes(&digits).await.unwrap();
+        assert_eq!(number.factors()[0].base().to_string(), query);
+        for invalid in [&b""[..], b"12a4", b"-15", "١٢".as_bytes()] {
+            assert!(matches!(
+                client.get_bytes(invalid).await,
+                Err(FactorDbError::InvalidNumber)
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unfactored() {
+        let mut server = mockito::Server::new_async().await;
+        let big = "10000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001";
+        test_utils::mock_query(
+            &mut server,
+            big,
+            &format!(
+                r#"{{"id":"1100000004567891234","status":"C","factors":[["{}",1]]}}"#,
+                big
+            ),
+        )
+        .create_async()
+        .await;
+        test_utils::mock_query(&mut server, "15", &test_utils::entry(15))
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        assert!(client.get(big).await.unwrap().is_unfactored());
+        assert!(!client.get(15).await.unwrap().is_unfactored());
+    }
+
+    #[tokio::test]
+    async fn test_totient_factorization() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 100..=101).await;
+        let client = test_utils::client(&server);
+        let prime = client.get(101).await.unwrap();
+        let totient = client.totient_factorization(&prime).await.unwrap();
+        assert_eq!(totient.value(), BigInt::from(100));
+        assert_eq!(
+            totient.into_prime_factorization(),
+            vec![(BigInt::from(2), 2), (BigInt::from(5), 2)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_totient_chain() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 0..=10).await;
+        let client = test_utils::client(&server);
+        assert_eq!(
+            client.totient_chain(10).await.unwrap(),
+            [10, 4, 2, 1].map(BigInt::from)
+        );
+        assert_eq!(client.totient_chain(1).await.unwrap(), [BigInt::from(1)]);
+        assert_eq!(client.totient_chain(0).await.unwrap(), [BigInt::from(0)]);
+    }
+
+    #[tokio::test]
+    async fn test_aliquot_sequence() {
+        let mut server = mockito::Server::new_async().await;
+        for n in [6, 220, 284, 12, 16, 15, 9, 4, 3, 1] {
+            test_utils::mock_range(&mut server, n..=n).await;
+        }
+        let client = test_utils::client(&server);
+        assert_eq!(
+            client.aliquot_sequence(220, 10).await.unwrap(),
+            [220, 284, 220].map(BigInt::from)
+        );
+        assert_eq!(
+            client.aliquot_sequence(6, 10).await.unwrap(),
+            [6, 6].map(BigInt::from)
+        );
+        // 12 -> 16 -> 15 -> 9 -> 4 -> 3 -> 1
+        assert_eq!(
+            client.aliquot_sequence(12, 10).await.unwrap(),
+            [12, 16, 15, 9, 4, 3, 1].map(BigInt::from)
+        );
+        assert_eq!(
+            client.aliquot_sequence(12, 2).await.unwrap(),
+            [12, 16, 15].map(BigInt::from)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_local() {
+        let mut server = mockito::Server::new_async().await;
+        for (n, name) in [(0, "zero"), (1, "one"), (17, "prime"), (360, "composite")] {
+            test_utils::mock_query(&mut server, &n.to_string(), &test_utils::fixture_json(name))
+                .create_async()
+                .await;
+        }
+        test_utils::mock_query(
+            &mut server,
+            "91",
+            r#"{"id":"91","status":"FF","factors":[["7",1],["13",1]]}"#,
+        )
+        .create_async()
+        .await;
+        // FactorDB claiming 21 = 3 * 5
+        test_utils::mock_query(
+            &mut server,
+            "21",
+            r#"{"id":"21","status":"FF","factors":[["3",1],["5",1]]}"#,
+        )
+        .create_async()
+        .await;
+        let client = test_utils::client(&server);
+        for n in [0, 1, 17, 360, 91] {
+            assert!(client.verify_local(n).await.unwrap(), "{}", n);
+        }
+        assert!(!client.verify_local(21).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_audit_range() {
+        let mut server = mockito::Server::new_async().await;
+        let responses = [
+            (
+                95,
+                r#"{"id":"95","status":"FF","factors":[["5",1],["19",1]]}"#,
+            ),
+            (
+                96,
+                r#"{"id":"96","status":"FF","factors":[["2",5],["3",1]]}"#,
+            ),
+            // wrong exponent
+            (97, r#"{"id":"97","status":"FF","factors":[["97",2]]}"#),
+            (
+                98,
+                r#"{"id":"98","status":"FF","factors":[["2",1],["7",2]]}"#,
+            ),
+            // missing a factor
+            (99, r#"{"id":"99","status":"CF","factors":[["3",2]]}"#),
+        ];
+        for (n, body) in responses {
+            test_utils::mock_query(&mut server, &n.to_string(), body)
+                .create_async()
+                .await;
+        }
+        let client = test_utils::client(&server);
+        let audit = client.audit_range(95, 5).await.unwrap();
+        let expected: Vec<(BigInt, bool)> =
+            [(95, true), (96, true), (97, false), (98, true), (99, false)]
+                .into_iter()
+                .map(|(n, agrees)| (BigInt::from(n), agrees))
+                .collect();
+        assert_eq!(audit, expected);
+    }
+
+    #[tokio::test]
+    async fn test_filter_numbers() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=10).await;
+        let client = test_utils::client(&server);
+        let primes = client
+            .filter_numbers(1..=10, |n| n.is_prime())
+            .await
+            .unwrap();
+        assert_eq!(primes, vec![2, 3, 5, 7]);
+        test_utils::mock_query(&mut server, "2^4", &test_utils::entry(16))
+            .create_async()
+            .await;
+        let squares = client
+            .filter_numbers(["2^4", "4", "5"], |n| n.is_square())
+            .await
+            .unwrap();
+        assert_eq!(squares, vec!["2^4", "4"]);
+    }
+
+    #[tokio::test]
+    async fn test_smallest_unfactored() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=10).await;
+        test_utils::mock_query(
+            &mut server,
+            "91",
+            r#"{"id":"91","status":"C","factors":[["91",1]]}"#,
+        )
+        .create_async()
+        .await;
+        let client = test_utils::client(&server);
+        let number = client
+            .smallest_unfactored(["10", "91", "2^4096+1"])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(number.value(), BigInt::from(91));
+        assert_eq!(number.status(), &NumberStatus::NoFactorsKnown);
+        assert!(client.smallest_unfactored(1..=10).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_many() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=20).await;
+        let client = test_utils::client(&server);
+        let numbers = ["12", "bad", "17", "20"];
+        for results in [
+            client.get_many(numbers).await,
+            client.get_many_with_concurrency(numbers, 1).await,
+        ] {
+            assert_eq!(results.len(), 4);
+            assert_eq!(results[0].as_ref().unwrap().id(), &BigInt::from(12));
+            assert!(results[1].is_err());
+            assert_eq!(results[2].as_ref().unwrap().id(), &BigInt::from(17));
+            assert_eq!(results[3].as_ref().unwrap().id(), &BigInt::from(20));
+        }
+        assert!(client.get_many(Vec::<u64>::new()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_try_get_many() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=7).await;
+        test_utils::mock_query(&mut server, "bad", "")
+            .with_status(404)
+            .create_async()
+            .await;
+        let mut unrequested = vec![];
+        for n in 8..=20 {
+            let mock = test_utils::mock_query(&mut server, &n.to_string(), &test_utils::entry(n))
+                .expect(0)
+                .create_async()
+                .await;
+            unrequested.push(mock);
+        }
+        let client = test_utils::client(&server);
+
+        let numbers = client.try_get_many(1..=7).await.unwrap();
+        assert_eq!(
+            numbers.iter().map(|n| n.id().clone()).collect::<Vec<_>>(),
+            (1..=7).map(BigInt::from).collect::<Vec<_>>()
+        );
+
+        // the first 8 numbers are requested straight away, and the error comes first, so the rest never are
+        let numbers = std::iter::once("bad".to_string()).chain((1..=20).map(|n| n.to_string()));
+        assert!(matches!(
+            client.try_get_many(numbers).await,
+            Err(FactorDbError::InvalidNumber)
+        ));
+        for mock in unrequested {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_with_factor_entries() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=12).await;
+        let client = test_utils::client(&server);
+        let (number, entries) = client.get_with_factor_entries(12).await.unwrap();
+        assert_eq!(number.id(), &BigInt::from(12));
+        assert_eq!(
+            entries.iter().map(|e| e.id().clone()).collect::<Vec<_>>(),
+            [2, 3].map(BigInt::from)
+        );
+        assert!(entries.iter().all(|e| e.is_definitely_prime()));
+        let (_, entries) = client.get_with_factor_entries(1).await.unwrap();
+        assert!(entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watch() {
+        let mut server = mockito::Server::new_async().await;
+        let bodies = [
+            r#"{"id":"1001","status":"C","factors":[["1001",1]]}"#,
+            r#"{"id":"1001","status":"C","factors":[["1001",1]]}"#,
+            r#"{"id":"1001","status":"CF","factors":[["7",1],["143",1]]}"#,
+            r#"{"id":"1001","status":"FF","factors":[["7",1],["11",1],["13",1]]}"#,
+        ];
+        for body in bodies {
+            test_utils::mock_query(&mut server, "1001", body)
+                .expect(1)
+                .create_async()
+                .await;
+        }
+        let client = test_utils::client(&server);
+        let snapshots: Vec<Number> = client
+            .watch(1001, Duration::from_millis(1))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let statuses: Vec<&NumberStatus> = snapshots.iter().map(|n| n.status()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                &NumberStatus::NoFactorsKnown,
+                &NumberStatus::FactorsKnown,
+                &NumberStatus::FullyFactored
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let semaphore = Arc::new(Semaphore::new(1));
+        let mut client = FactorDbClient::builder()
+            .concurrency_limiter(semaphore.clone())
+            .build()
+            .unwrap();
+        client.endpoint = format!("{}/api", server.url());
+
+        // while someone else holds the only permit, requests have to wait
+        let permit = semaphore.clone().acquire_owned().await.unwrap();
+        let blocked = tokio::time::timeout(Duration::from_millis(100), client.get(15)).await;
+        assert!(blocked.is_err());
+        drop(permit);
+        assert!(client.get(15).await.is_ok());
+        assert_eq!(semaphore.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter_covers_body() {
+        use std::io::{Read, Write};
+
+        // responds with the headers straight away, but only sends the body once told to
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/api", listener.local_addr().unwrap());
+        let (send_body, body_sent) = std::sync::mpsc::channel::<()>();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).unwrap();
+            let body = test_utils::entry(15);
+            let headers = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(headers.as_bytes()).unwrap();
+            stream.flush().unwrap();
+            body_sent.recv().unwrap();
+            stream.write_all(body.as_bytes()).unwrap();
+        });
+        let semaphore = Arc::new(Semaphore::new(1));
+        let client = FactorDbClient::builder()
+            .endpoint(endpoint)
+            .concurrency_limiter(semaphore.clone())
+            .build()
+            .unwrap();
+        let request = tokio::spawn(async move { client.get(15).await });
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(semaphore.available_permits(), 0);
+        send_body.send(()).unwrap();
+        assert_eq!(request.await.unwrap().unwrap().value(), BigInt::from(15));
+        assert_eq!(semaphore.available_permits(), 1);
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_user_agent() {
+        let mut server = mockito::Server::new_async().await;
+        let default = test_utils::mock_query(&mut server, "15", &test_utils::entry(15))
+            .match_header(
+                "user-agent",
+                format!("factordb-rust/{}", env!("CARGO_PKG_VERSION")).as_str(),
+            )
+            .expect(2)
+            .create_async()
+            .await;
+        let custom = test_utils::mock_query(&mut server, "15", &test_utils::entry(15))
+            .match_header("user-agent", "factordb-test")
+            .expect(1)
+            .create_async()
+            .await;
+        let endpoint = format!("{}/api", server.url());
+        let builders = [
+            FactorDbClient::builder(),
+            FactorDbClient::builder().user_agent("factordb-test"),
+        ];
+        test_utils::client(&server).get(15).await.unwrap();
+        for builder in builders {
+            builder
+                .endpoint(&endpoint)
+                .build()
+                .unwrap()
+                .get(15)
+                .await
+                .unwrap();
+        }
+        default.assert_async().await;
+        custom.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_builder_retry_policy() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = test_utils::mock_query(&mut server, "15", "")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let client = FactorDbClient::builder()
+            .endpoint(format!("{}/api", server.url()))
+            .retry_policy(RetryPolicy {
+                max_retries: 1,
+                base_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(1),
+            })
+            .build()
+            .unwrap();
+        assert_eq!(client.get(15).await.unwrap().value(), BigInt::from(15));
+        unavailable.assert_async().await;
+        assert_eq!(client.request_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_max_response_bytes() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let padded = format!("{}{}", test_utils::entry(16), " ".repeat(1000));
+        test_utils::mock_query(&mut server, "16", &padded)
+            .create_async()
+            .await;
+        test_utils::mock_query(&mut server, "17", "")
+            .with_chunked_body(move |w| {
+                for _ in 0..100 {
+                    w.write_all(&[b' '; 100])?;
+                }
+                Ok(())
+            })
+            .create_async()
+            .await;
+        let client = FactorDbClient::builder()
+            .endpoint(format!("{}/api", server.url()))
+            .max_response_bytes(200)
+            .build()
+            .unwrap();
+        assert!(client.get(15).await.is_ok());
+        assert!(client.get_json(15).await.is_ok());
+        for query in [16, 17] {
+            assert!(matches!(
+                client.get(query).await,
+                Err(FactorDbError::ResponseTooLarge(200))
+            ));
+        }
+        assert!(matches!(
+            client.get_json(17).await,
+            Err(FactorDbError::ResponseTooLarge(200))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_json_decoding() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_query(&mut server, "15", "\"é\"")
+            .with_header("content-type", "text/html; charset=iso-8859-1")
+            .create_async()
+            .await;
+        for max_response_bytes in [None, Some(200)] {
+            let mut builder = FactorDbClient::builder().endpoint(format!("{}/api", server.url()));
+            if let Some(limit) = max_response_bytes {
+                builder = builder.max_response_bytes(limit);
+            }
+            let client = builder.build().unwrap();
+            assert_eq!(client.get_json(15).await.unwrap(), "\"é\"");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_keepalive() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        let mut client = FactorDbClient::builder().no_keepalive().build().unwrap();
+        client.endpoint = format!("{}/api", server.url());
+        for _ in 0..2 {
+            assert_eq!(
+                client.get(15).await.unwrap().into_factors_flattened(),
+                vec![BigInt::from(3), BigInt::from(5)]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_from_env() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_query(&mut server, "15", &test_utils::entry(15))
+            .match_header("user-agent", "factordb-test")
+            .create_async()
+            .await;
+        let endpoint = format!("{}/api", server.url());
+        let from_vars = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            FactorDbClient::from_lookup(|name| {
+                vars.get(name)
+                    .cloned()
+                    .ok_or(std::env::VarError::NotPresent)
+            })
+        };
+        let client = from_vars(&[
+            ("FACTORDB_ENDPOINT", &endpoint),
+            ("FACTORDB_TIMEOUT_MS", "5000"),
+            ("FACTORDB_USER_AGENT", "factordb-test"),
+        ])
+        .unwrap();
+        assert_eq!(client.endpoint, endpoint);
+        assert!(client.get(15).await.is_ok());
+
+        assert!(matches!(
+            from_vars(&[("FACTORDB_TIMEOUT_MS", "soon")]),
+            Err(FactorDbError::InvalidEnvVar(name)) if name == "FACTORDB_TIMEOUT_MS"
+        ));
+        assert!(matches!(
+            from_vars(&[
+                ("FACTORDB_TIMEOUT_MS", ""),
+                ("FACTORDB_ENDPOINT", "factordb.com/api")
+            ]),
+            Err(FactorDbError::InvalidEndpoint(_))
+        ));
+        assert_eq!(from_vars(&[]).unwrap().endpoint, ENDPOINT);
+        assert!(matches!(
+            FactorDbClient::from_lookup(|_| Err(std::env::VarError::NotUnicode(Default::default()))),
+            Err(FactorDbError::InvalidEnvVar(name)) if name == "FACTORDB_ENDPOINT"
+        ));
+    }
+
+    #[test]
+    fn test_async_std() {
+        let mut server = mockito::Server::new();
+        test_utils::mock_query(&mut server, "15", &test_utils::entry(15)).create();
+        let client = test_utils::client(&server);
+        let number = async_std::task::block_on(async_compat::Compat::new(client.get(15))).unwrap();
+        assert_eq!(
+            number.into_factors_flattened(),
+            vec![BigInt::from(3), BigInt::from(5)]
+        );
+    }
+
+    #[test]
+    fn test_send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<FactorDbClient>();
+        assert_send_sync::<FactorDbError>();
+        assert_send_sync::<Number>();
+    }
+}
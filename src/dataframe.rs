@@ -0,0 +1,42 @@
+use crate::Number;
+
+/// Splits `numbers` into columns of their values, statuses and factorizations, ready to be loaded into a DataFrame.
+///
+/// All three columns have one entry per number, in the same order as `numbers`. Values are in decimal, statuses use the
+/// same codes as FactorDB (e.g. `FF` or `Prp`, formatted by [`NumberStatus`](crate::NumberStatus)), and factorizations
+/// have every prime factor repeated by its exponent and separated by spaces, in the same format as [`Number`]'s
+/// [`Display`](std::fmt::Display) implementation.
+pub fn numbers_to_columns(numbers: &[Number]) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut values = Vec::with_capacity(numbers.len());
+    let mut statuses = Vec::with_capacity(numbers.len());
+    let mut factorizations = Vec::with_capacity(numbers.len());
+    for number in numbers {
+        values.push(number.value().to_string());
+        statuses.push(number.status().to_string());
+        factorizations.push(number.to_string());
+    }
+    (values, statuses, factorizations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[test]
+    fn test_numbers_to_columns() {
+        let numbers = ["composite", "prime", "partial", "one"].map(test_utils::load_fixture);
+        let (values, statuses, factorizations) = numbers_to_columns(&numbers);
+        assert_eq!(values, vec!["360", "17", "3000000048000000189", "1"]);
+        assert_eq!(statuses, vec!["FF", "P", "CF", "Unit"]);
+        assert_eq!(
+            factorizations,
+            vec!["2 2 2 3 3 5", "17", "3 1000000016000000063", "1"]
+        );
+        assert_eq!(numbers_to_columns(&[]), (vec![], vec![], vec![]));
+
+        let prp: Number =
+            serde_json::from_str(r#"{"id":"1","status":"PRP","factors":[["17",1]]}"#).unwrap();
+        assert_eq!(numbers_to_columns(&[prp]).1, vec!["Prp"]);
+    }
+}
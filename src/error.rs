@@ -0,0 +1,90 @@
+use num_bigint::BigInt;
+
+/// Error type in this crate.
+#[derive(thiserror::Error, Debug)]
+pub enum FactorDbError {
+    /// Request error
+    #[cfg(feature = "client")]
+    #[error("Request error: {0}")]
+    RequestError(#[from] reqwest::Error),
+    /// Invalid number, i.e. FactorDB responded with a client error (HTTP 4xx other than 429)
+    #[error("Invalid number")]
+    InvalidNumber,
+    /// The request didn't complete within the configured timeout
+    #[cfg(feature = "client")]
+    #[error("Request timed out")]
+    Timeout,
+    /// FactorDB is rate limiting requests (HTTP 429)
+    #[cfg(feature = "client")]
+    #[error("Rate limited by FactorDB")]
+    RateLimited {
+        /// How many seconds to wait before trying again, if FactorDB said so
+        retry_after: Option<u64>,
+    },
+    /// FactorDB responded with a server error (HTTP 5xx)
+    #[cfg(feature = "client")]
+    #[error("Server error: {0}")]
+    ServerError(reqwest::StatusCode),
+    /// FactorDB responded with a status which is neither successful nor an error (HTTP 1xx or 3xx)
+    #[cfg(feature = "client")]
+    #[error("Unexpected status: {0}")]
+    UnexpectedStatus(reqwest::StatusCode),
+    /// The API endpoint isn't a valid HTTP(S) URL
+    #[error("Invalid endpoint: {0}")]
+    InvalidEndpoint(String),
+    /// An environment variable read by [`FactorDbClient::from_env()`](crate::FactorDbClient::from_env) has an invalid
+    /// value
+    #[cfg(feature = "client")]
+    #[error("Invalid value for environment variable {0}")]
+    InvalidEnvVar(String),
+    /// The response (or other JSON) couldn't be parsed, or a number couldn't be serialized to JSON
+    #[error("Parse error: {0}")]
+    ParseError(#[from] serde_json::Error),
+    /// The response body couldn't be read or decoded
+    #[cfg(feature = "client")]
+    #[error("Decode error: {0}")]
+    DecodeError(reqwest::Error),
+    /// The response body is larger than the maximum size the client was configured with
+    #[cfg(feature = "client")]
+    #[error("Response larger than {0} bytes")]
+    ResponseTooLarge(usize),
+    /// The number isn't fully factored yet, see
+    /// [`FactorDbClient::get_fully_factored()`](crate::FactorDbClient::get_fully_factored). Contains the partial
+    /// result.
+    #[cfg(feature = "client")]
+    #[error("Not fully factored: {0}")]
+    NotFullyFactored(Box<crate::Number>),
+    /// A value is too large to be converted to the requested type
+    #[error("Value too large: {0}")]
+    Overflow(BigInt),
+}
+
+impl FactorDbError {
+    /// Returns `true` if this error is likely transient, so sending the same request again later may succeed.
+    ///
+    /// This is the case for rate limiting (HTTP 429), server errors (HTTP 5xx), and requests which failed to connect,
+    /// timed out or had their connection reset. Errors caused by the request itself, such as
+    /// [`FactorDbError::InvalidNumber`], or by the response, such as [`FactorDbError::ParseError`], are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            #[cfg(feature = "client")]
+            FactorDbError::RateLimited { .. }
+            | FactorDbError::ServerError(_)
+            | FactorDbError::Timeout => true,
+            #[cfg(feature = "client")]
+            FactorDbError::RequestError(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if FactorDB rejected the request with a client error (HTTP 4xx), i.e. this is
+    /// [`FactorDbError::InvalidNumber`] or `RateLimited`.
+    pub fn is_client_error(&self) -> bool {
+        match self {
+            FactorDbError::InvalidNumber => true,
+            #[cfg(feature = "client")]
+            FactorDbError::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+}
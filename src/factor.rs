@@ -5,17 +5,32 @@ use std::fmt::{Display, Formatter};
 use num_bigint::BigInt;
 use serde::{Deserialize, Serialize};
 
-use crate::utils::{deserialize_string_to_bigint, deserialize_u64_to_bigint};
+use crate::utils::{
+    deserialize_string_to_bigint, deserialize_u64_to_bigint, serialize_bigint_to_string,
+    serialize_bigint_to_u64,
+};
 
 /// A struct representing a factor with a unique base, along with the exponent (i.e. how many times
 /// the factor is repeated).
 #[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Factor(
-    #[serde(deserialize_with = "deserialize_string_to_bigint")] BigInt,
-    #[serde(deserialize_with = "deserialize_u64_to_bigint")] BigInt,
+    #[serde(
+        deserialize_with = "deserialize_string_to_bigint",
+        serialize_with = "serialize_bigint_to_string"
+    )]
+    BigInt,
+    #[serde(
+        deserialize_with = "deserialize_u64_to_bigint",
+        serialize_with = "serialize_bigint_to_u64"
+    )]
+    BigInt,
 );
 
 impl Factor {
+    pub(crate) fn new(base: BigInt, exponent: BigInt) -> Self {
+        Self(base, exponent)
+    }
+
     /// Returns the base as a [`BigInt`].
     pub fn base(&self) -> &BigInt {
         &self.0
@@ -26,8 +41,28 @@ impl Factor {
         &self.1
     }
 
+    /// Returns the base and exponent as a tuple of references.
+    pub fn as_pair(&self) -> (&BigInt, &BigInt) {
+        (&self.0, &self.1)
+    }
+
+    /// Computes `base ^ exponent` by repeated squaring, which is much faster than multiplying the values of
+    /// [`Self::iter()`] for large exponents.
+    ///
+    /// # Panics
+    /// Panics if the exponent doesn't fit in a [`u32`]. Use [`Self::checked_pow()`] to handle that case.
+    pub fn pow(&self) -> BigInt {
+        self.checked_pow().expect("exponent too large")
+    }
+
+    /// Same as [`Self::pow()`], but returns `None` if the exponent doesn't fit in a [`u32`].
+    pub fn checked_pow(&self) -> Option<BigInt> {
+        let exp = u32::try_from(&self.1).ok()?;
+        Some(self.0.pow(exp))
+    }
+
     /// Iterate over the base by the exponent value.
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
             base: &self.0,
             remaining_exp: self.1.clone(),
@@ -35,8 +70,45 @@ impl Factor {
     }
 }
 
+/// Factors `n` locally by trial division, returning its prime factors in ascending order.
+///
+/// Like FactorDB, 0 and 1 are returned as a single factor of themselves. Trial division is fast enough for numbers
+/// without large prime factors, but can take a while (up to about 2^32 divisions) for numbers with two large ones.
+pub fn factor_local(mut n: u64) -> Vec<Factor> {
+    if n < 2 {
+        return vec![Factor(BigInt::from(n), BigInt::from(1))];
+    }
+    let mut factors = vec![];
+    let mut p = 2;
+    while p <= n / p {
+        let mut exp = 0u32;
+        while n.is_multiple_of(p) {
+            n /= p;
+            exp += 1;
+        }
+        if exp > 0 {
+            factors.push(Factor(BigInt::from(p), BigInt::from(exp)));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        factors.push(Factor(BigInt::from(n), BigInt::from(1)));
+    }
+    factors
+}
+
+/// Formats the base repeated by the exponent, separated by spaces (e.g. `2 2 2`).
+///
+/// The alternate form (`{:#}`) uses exponent notation instead (e.g. `2^3`), leaving out exponents of 1.
 impl Display for Factor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return if self.1 == BigInt::from(1) {
+                write!(f, "{}", self.0)
+            } else {
+                write!(f, "{}^{}", self.0, self.1)
+            };
+        }
         write!(
             f,
             "{}",
@@ -48,6 +120,13 @@ impl Display for Factor {
     }
 }
 
+impl From<Factor> for (BigInt, BigInt) {
+    /// Converts a [`Factor`] into a `(base, exponent)` tuple.
+    fn from(factor: Factor) -> Self {
+        (factor.0, factor.1)
+    }
+}
+
 impl IntoIterator for Factor {
     type Item = BigInt;
     type IntoIter = IntoIter;
@@ -126,6 +205,60 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_factor_pair() {
+        let factor = Factor(BigInt::from(2), BigInt::from(3));
+        assert_eq!(factor.as_pair(), (&BigInt::from(2), &BigInt::from(3)));
+        let (base, exp): (BigInt, BigInt) = factor.into();
+        assert_eq!(base, BigInt::from(2));
+        assert_eq!(exp, BigInt::from(3));
+    }
+
+    #[test]
+    fn test_factor_display() {
+        let factor = Factor(BigInt::from(2), BigInt::from(3));
+        assert_eq!(factor.to_string(), "2 2 2");
+        assert_eq!(format!("{:#}", factor), "2^3");
+        assert_eq!(
+            format!("{:#}", Factor(BigInt::from(5), BigInt::from(1))),
+            "5"
+        );
+    }
+
+    #[test]
+    fn test_factor_pow() {
+        let factor = Factor(BigInt::from(2), BigInt::from(64));
+        assert_eq!(factor.pow(), BigInt::from(u64::MAX) + 1);
+        assert_eq!(factor.checked_pow(), Some(factor.iter().product()));
+        assert_eq!(
+            Factor(BigInt::from(2), BigInt::from(u64::from(u32::MAX) + 1)).checked_pow(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_factor_local() {
+        assert_eq!(
+            factor_local(360),
+            vec![
+                Factor(BigInt::from(2), BigInt::from(3)),
+                Factor(BigInt::from(3), BigInt::from(2)),
+                Factor(BigInt::from(5), BigInt::from(1))
+            ]
+        );
+        assert_eq!(
+            factor_local(4_294_967_291 * 3),
+            vec![
+                Factor(BigInt::from(3), BigInt::from(1)),
+                Factor(BigInt::from(4_294_967_291u64), BigInt::from(1))
+            ]
+        );
+        assert_eq!(
+            factor_local(1),
+            vec![Factor(BigInt::from(1), BigInt::from(1))]
+        );
+    }
+
     #[test]
     fn test_factor_into_iter() {
         let a_million = Factor(BigInt::from(10), BigInt::from(6));
@@ -1,22 +1,51 @@
 use clap::Parser;
-use factordb::FactorDbBlockingClient;
+use factordb::{FactorDbBlockingClient, FactorDbClient, FactorDbError, Number, NumberStatus};
+use futures::{future, stream, Stream, StreamExt};
 use human_panic::setup_panic;
+use num_bigint::BigInt;
+use std::io::{self, BufRead};
+use std::num::NonZeroUsize;
 use std::{fmt::Display, process::exit};
 
 /// Finds a factor to a number using FactorDB (http://factordb.com/)
 #[derive(Parser, Debug)]
 #[clap(version, about, long_about = None)]
 struct Cli {
-    /// Number to find its factor
+    /// Numbers to find their factors, read from stdin (separated by whitespace) if none are given or in place of `-`
     numbers: Vec<String>,
 
     /// Print unique factors on each line
     #[clap(long)]
     unique: bool,
 
+    /// Print factors in exponent form (e.g. 2^2 5^2) on each line
+    #[clap(long, conflicts_with = "unique")]
+    exponents: bool,
+
     /// Print JSON output of FactorDB API
     #[clap(long)]
     json: bool,
+
+    /// Print each result as a compact JSON object on its own line (JSON Lines)
+    #[clap(long, conflicts_with = "json")]
+    jsonl: bool,
+
+    /// Print results as CSV with the columns input, status and factors
+    #[clap(long, conflicts_with_all = ["json", "jsonl"])]
+    csv: bool,
+
+    /// Warn if the factors of a fully factored number don't multiply back to it (only for integers and expressions
+    /// using + - * / ^ ! and parentheses)
+    #[clap(long, conflicts_with = "json")]
+    verify: bool,
+
+    /// Look up up to this many numbers at a time, printing the results in the same order as the input
+    #[clap(long, default_value = "1")]
+    jobs: NonZeroUsize,
+
+    /// Send requests to this API endpoint instead of http://factordb.com/api, e.g. a mirror or https://factordb.com/api
+    #[clap(long)]
+    endpoint: Option<String>,
 }
 
 fn print_error<M: Display, V: Display>(msg: M, input_value: V) -> ! {
@@ -26,35 +55,464 @@ fn print_error<M: Display, V: Display>(msg: M, input_value: V) -> ! {
     exit(1)
 }
 
-fn main() {
-    env_logger::init();
-    setup_panic!();
-    let cli = Cli::parse();
-    let client = FactorDbBlockingClient::new();
-
-    for number in cli.numbers {
-        if cli.json {
-            match client.get_json(&number) {
-                Ok(text) => println!("{}", text),
-                Err(e) => print_error(e, number),
+fn format_exponents(number: &Number) -> String {
+    number
+        .factors()
+        .iter()
+        .map(|f| format!("{:#}", f))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads whitespace-separated numbers from `reader`, skipping blank lines.
+fn read_numbers(reader: impl BufRead) -> io::Result<Vec<String>> {
+    let mut numbers = vec![];
+    for line in reader.lines() {
+        numbers.extend(line?.split_whitespace().map(str::to_string));
+    }
+    Ok(numbers)
+}
+
+/// Returns the numbers to look up: the arguments, with `-` replaced by the numbers read from stdin, or only the
+/// numbers from stdin if there are no arguments.
+fn input_numbers(args: Vec<String>, stdin: impl BufRead) -> io::Result<Vec<String>> {
+    if args.is_empty() {
+        return read_numbers(stdin);
+    }
+    let mut stdin = Some(stdin);
+    let mut numbers = vec![];
+    for arg in args {
+        if arg == "-" {
+            // stdin can only be read once, so any further `-` are just ignored
+            if let Some(stdin) = stdin.take() {
+                numbers.extend(read_numbers(stdin)?);
             }
         } else {
-            match client.get(&number) {
-                Ok(num) => {
-                    if cli.unique {
-                        println!(
-                            "{}",
-                            num.into_unique_factors()
-                                .iter()
-                                .map(|f| f.to_string())
-                                .collect::<Vec<_>>()
-                                .join(" ")
-                        )
-                    } else {
-                        println!("{}", num)
-                    }
+            numbers.push(arg);
+        }
+    }
+    Ok(numbers)
+}
+
+/// Quotes `field` for CSV if it contains a delimiter, quote, line break or space, doubling any quotes in it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n', ' ']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Formats a CSV row with the columns `input,status,factors` for `number`.
+fn csv_row(input: &str, number: Number) -> String {
+    let status = number.status().to_string();
+    let factors = number
+        .into_factors_flattened()
+        .iter()
+        .map(|f| f.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    [input, &status, &factors].map(csv_field).join(",")
+}
+
+/// Largest result (in bits) [`evaluate()`] computes, so that an input like `10^10^10` can't exhaust memory.
+const MAX_EVALUATED_BITS: u64 = 1 << 20;
+
+/// Evaluates an integer expression such as `2^64+1` or `(10!-1)/3`, returning `None` if it uses anything else (such
+/// as FactorDB's functions), doesn't divide evenly or gets too large.
+///
+/// `^` binds tightest and is right-associative, followed by unary `-`, then `*` and `/`, then `+` and `-`.
+fn evaluate(expression: &str) -> Option<BigInt> {
+    let tokens: Vec<char> = expression.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut parser = ExpressionParser { tokens, pos: 0 };
+    let value = parser.sum()?;
+    (parser.pos == parser.tokens.len()).then_some(value)
+}
+
+/// Recursive descent parser behind [`evaluate()`], one method per precedence level.
+struct ExpressionParser {
+    tokens: Vec<char>,
+    pos: usize,
+}
+
+impl ExpressionParser {
+    fn eat(&mut self, token: char) -> bool {
+        let matches = self.tokens.get(self.pos) == Some(&token);
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    fn sum(&mut self) -> Option<BigInt> {
+        let mut value = self.product()?;
+        loop {
+            if self.eat('+') {
+                value += self.product()?;
+            } else if self.eat('-') {
+                value -= self.product()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Option<BigInt> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat('*') {
+                value *= self.unary()?;
+            } else if self.eat('/') {
+                let divisor = self.unary()?;
+                if divisor == BigInt::from(0) || &value % &divisor != BigInt::from(0) {
+                    return None;
                 }
-                Err(e) => print_error(e, number),
+                value /= divisor;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Option<BigInt> {
+        if self.eat('-') {
+            Some(-self.unary()?)
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Option<BigInt> {
+        let base = self.factorial()?;
+        if !self.eat('^') {
+            return Some(base);
+        }
+        let exponent = u32::try_from(self.unary()?).ok()?;
+        if base.bits().saturating_mul(u64::from(exponent)) > MAX_EVALUATED_BITS {
+            return None;
+        }
+        Some(base.pow(exponent))
+    }
+
+    fn factorial(&mut self) -> Option<BigInt> {
+        let mut value = self.atom()?;
+        while self.eat('!') {
+            // n! has fewer than n * log2(n) bits
+            let n = u64::try_from(&value).ok()?;
+            if n.saturating_mul(u64::from(64 - n.leading_zeros())) > MAX_EVALUATED_BITS {
+                return None;
+            }
+            value = (1..=n).map(BigInt::from).product();
+        }
+        Some(value)
+    }
+
+    fn atom(&mut self) -> Option<BigInt> {
+        if self.eat('(') {
+            let value = self.sum()?;
+            return self.eat(')').then_some(value);
+        }
+        let start = self.pos;
+        while self.tokens.get(self.pos).is_some_and(char::is_ascii_digit) {
+            self.pos += 1;
+        }
+        self.tokens[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+}
+
+/// Returns a warning if `number` is fully factored but its factors don't multiply to `input`.
+///
+/// Expressions are evaluated with [`evaluate()`]; inputs it can't evaluate aren't checked.
+fn verify(input: &str, number: &Number) -> Option<String> {
+    let expected = evaluate(input)?;
+    let fully_factored = matches!(
+        number.status(),
+        NumberStatus::FullyFactored | NumberStatus::DefinitelyPrime | NumberStatus::ProbablyPrime
+    );
+    let product = number.value();
+    (fully_factored && product != expected)
+        .then(|| format!("factors multiply to {} instead of {}", product, expected))
+}
+
+/// The result of looking up a number: the raw JSON response with `--json`, otherwise the parsed entry.
+enum Lookup {
+    Json(String),
+    Number(Number),
+}
+
+/// Prints the result of looking up `input` in the format selected by `cli`, exiting on errors.
+fn print_lookup(cli: &Cli, input: &str, result: Result<Lookup, FactorDbError>) {
+    let num = match result {
+        Ok(Lookup::Json(text)) => return println!("{}", text),
+        Ok(Lookup::Number(num)) => num,
+        Err(e) => print_error(e, input),
+    };
+    if cli.verify {
+        if let Some(warning) = verify(input, &num) {
+            eprintln!("warning: {}: {}", input, warning);
+        }
+    }
+    if cli.csv {
+        println!("{}", csv_row(input, num))
+    } else if cli.jsonl {
+        match serde_json::to_string(&num) {
+            Ok(line) => println!("{}", line),
+            Err(e) => print_error(e, input),
+        }
+    } else if cli.unique {
+        println!(
+            "{}",
+            num.into_unique_factors()
+                .iter()
+                .map(|f| f.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    } else if cli.exponents {
+        println!("{}", format_exponents(&num))
+    } else {
+        println!("{}", num)
+    }
+}
+
+/// Looks up `numbers` one at a time with the blocking client.
+fn run_serial(cli: &Cli, numbers: Vec<String>) {
+    let client = match &cli.endpoint {
+        Some(endpoint) => FactorDbBlockingClient::with_endpoint(endpoint)
+            .unwrap_or_else(|e| print_error(e, endpoint)),
+        None => FactorDbBlockingClient::new(),
+    };
+    for number in numbers {
+        let result = if cli.json {
+            client.get_json(&number).map(Lookup::Json)
+        } else {
+            client.get(&number).map(Lookup::Number)
+        };
+        print_lookup(cli, &number, result);
+    }
+}
+
+/// Looks up up to `cli.jobs` of `numbers` at a time with the async client, printing the results in input order.
+fn run_concurrent(cli: &Cli, numbers: Vec<String>) {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => print_error(e, "runtime"),
+    };
+    let client = match &cli.endpoint {
+        Some(endpoint) => {
+            FactorDbClient::with_endpoint(endpoint).unwrap_or_else(|e| print_error(e, endpoint))
+        }
+        None => FactorDbClient::new(),
+    };
+    runtime.block_on(
+        lookup_concurrent(&client, cli.json, numbers, cli.jobs.get()).for_each(
+            |(number, result)| {
+                print_lookup(cli, &number, result);
+                future::ready(())
+            },
+        ),
+    );
+}
+
+/// Looks up up to `jobs` of `numbers` at a time with `client`, yielding each number with its result in input order.
+fn lookup_concurrent(
+    client: &FactorDbClient,
+    json: bool,
+    numbers: Vec<String>,
+    jobs: usize,
+) -> impl Stream<Item = (String, Result<Lookup, FactorDbError>)> + '_ {
+    stream::iter(numbers)
+        .map(move |number| async move {
+            let result = if json {
+                client.get_json(&number).await.map(Lookup::Json)
+            } else {
+                client.get(&number).await.map(Lookup::Number)
+            };
+            (number, result)
+        })
+        .buffered(jobs)
+}
+
+fn main() {
+    env_logger::init();
+    setup_panic!();
+    let mut cli = Cli::parse();
+    if cli.csv {
+        println!("input,status,factors");
+    }
+
+    let numbers = match input_numbers(std::mem::take(&mut cli.numbers), io::stdin().lock()) {
+        Ok(numbers) => numbers,
+        Err(e) => print_error(e, "stdin"),
+    };
+    if cli.jobs.get() > 1 {
+        run_concurrent(&cli, numbers);
+    } else {
+        run_serial(&cli, numbers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_exponents() {
+        let hundred: Number =
+            serde_json::from_str(r#"{"id":"100","status":"FF","factors":[["2",2],["5",2]]}"#)
+                .unwrap();
+        assert_eq!(format_exponents(&hundred), "2^2 5^2");
+        let thirty: Number = serde_json::from_str(
+            r#"{"id":"30","status":"FF","factors":[["2",1],["3",1],["5",1]]}"#,
+        )
+        .unwrap();
+        assert_eq!(format_exponents(&thirty), "2 3 5");
+    }
+
+    #[test]
+    fn test_json_flags_conflict() {
+        assert!(Cli::try_parse_from(["factordb", "--jsonl", "--verify", "42"]).is_ok());
+        assert!(Cli::try_parse_from(["factordb", "--jobs", "0", "42"]).is_err());
+        let error = Cli::try_parse_from(["factordb", "--json", "--jsonl", "42"]).unwrap_err();
+        assert_eq!(error.kind(), clap::error::ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn test_input_numbers() {
+        let stdin = || io::Cursor::new("15 16\n\n  2^64+1\n");
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            input_numbers(vec![], stdin()).unwrap(),
+            args(&["15", "16", "2^64+1"])
+        );
+        assert_eq!(
+            input_numbers(args(&["1", "-", "2", "-"]), stdin()).unwrap(),
+            args(&["1", "15", "16", "2^64+1", "2"])
+        );
+        assert_eq!(
+            input_numbers(args(&["1", "2"]), stdin()).unwrap(),
+            args(&["1", "2"])
+        );
+    }
+
+    #[test]
+    fn test_csv_row() {
+        let hundred: Number =
+            serde_json::from_str(r#"{"id":"100","status":"FF","factors":[["2",2],["5",2]]}"#)
+                .unwrap();
+        assert_eq!(csv_row("10^2", hundred), r#"10^2,FF,"2 2 5 5""#);
+        let prime: Number =
+            serde_json::from_str(r#"{"id":"17","status":"P","factors":[["17",1]]}"#).unwrap();
+        assert_eq!(csv_row("17", prime), "17,P,17");
+        assert_eq!(csv_field(r#"a,"b""#), r#""a,""b""""#);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let cases = [
+            ("100", Some(100)),
+            ("2^64+1", Some(18446744073709551617i128)),
+            ("2^3^2", Some(512)),
+            ("-2^2", Some(-4)),
+            ("(10!-1)/3", None),
+            ("(10!+2)/2", Some(1814401)),
+            ("2*(3+4)-5", Some(9)),
+            ("1-2-3", Some(-4)),
+            ("100000000!", None),
+            ("3!!", Some(720)),
+            ("10^10^10", None),
+            ("M(7)", None),
+            ("2^", None),
+            ("(1", None),
+            ("", None),
+        ];
+        for (expression, expected) in cases {
+            assert_eq!(
+                evaluate(expression),
+                expected.map(BigInt::from),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let hundred: Number =
+            serde_json::from_str(r#"{"id":"100","status":"FF","factors":[["2",2],["5",2]]}"#)
+                .unwrap();
+        assert_eq!(verify("100", &hundred), None);
+        assert_eq!(verify("10^2", &hundred), None);
+        assert_eq!(
+            verify("10^3/10-1", &hundred).as_deref(),
+            Some("factors multiply to 100 instead of 99")
+        );
+        assert_eq!(verify("M(7)", &hundred), None);
+        let missing_factor: Number =
+            serde_json::from_str(r#"{"id":"100","status":"FF","factors":[["2",2],["5",1]]}"#)
+                .unwrap();
+        assert_eq!(
+            verify("100", &missing_factor).as_deref(),
+            Some("factors multiply to 20 instead of 100")
+        );
+        // partial factorizations aren't expected to multiply to anything in particular
+        let partial: Number =
+            serde_json::from_str(r#"{"id":"100","status":"CF","factors":[["2",2]]}"#).unwrap();
+        assert_eq!(verify("100", &partial), None);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_concurrent_order() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = |server: &mut mockito::ServerGuard, query: &str| {
+            server
+                .mock("GET", "/api")
+                .match_query(mockito::Matcher::UrlEncoded("query".into(), query.into()))
+                .with_header("content-type", "application/json")
+        };
+        let entry = |n: u64| format!(r#"{{"id":"{}","status":"FF","factors":[["{}",1]]}}"#, n, n);
+        // the first response is held back until the last one has been sent, so they complete out of order
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let receiver = std::sync::Mutex::new(receiver);
+        mock(&mut server, "2")
+            .with_chunked_body(move |w| {
+                let delivered = receiver
+                    .lock()
+                    .unwrap()
+                    .recv_timeout(std::time::Duration::from_secs(5));
+                assert!(delivered.is_ok(), "last response wasn't sent first");
+                w.write_all(entry(2).as_bytes())
+            })
+            .create_async()
+            .await;
+        mock(&mut server, "3")
+            .with_body(entry(3))
+            .create_async()
+            .await;
+        mock(&mut server, "5")
+            .with_chunked_body(move |w| {
+                w.write_all(entry(5).as_bytes())?;
+                sender.send(()).ok();
+                Ok(())
+            })
+            .create_async()
+            .await;
+
+        let client = FactorDbClient::with_endpoint(format!("{}/api", server.url())).unwrap();
+        let numbers = vec!["2".to_string(), "3".to_string(), "5".to_string()];
+        let results: Vec<_> = lookup_concurrent(&client, false, numbers.clone(), 3)
+            .collect()
+            .await;
+        let inputs: Vec<_> = results.iter().map(|(input, _)| input.clone()).collect();
+        assert_eq!(inputs, numbers);
+        for (input, result) in results {
+            match result {
+                Ok(Lookup::Number(number)) => assert_eq!(number.value().to_string(), input),
+                _ => panic!("lookup of {} failed", input),
             }
         }
     }
@@ -2,27 +2,52 @@
 //!
 //! Both [`Number`] and [`NumberStatus`] are re-exported so importing this module directly isn't necessary.
 
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
 
-use num_bigint::BigInt;
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::ToPrimitive;
 
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Serialize};
 
-use crate::utils::deserialize_id;
-use crate::Factor;
+use crate::utils::{deserialize_id, deserialize_sorted, log2, serialize_bigint_to_string};
+use crate::{Factor, FactorDbError};
 
 /// A number entry in FactorDB. Contains the number itself, its status in the database as well as its
 /// factors.
-#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+///
+/// Besides FactorDB's own field names (`id`, `status` and `factors`), responses using `number_id`, `state` and
+/// `factorization` instead are accepted as well, in case a mirror names them differently. Serializing always uses
+/// FactorDB's names.
+///
+/// Comparing and hashing numbers only takes the FactorDB entry into account, so the same entry is equal no matter
+/// which query it was fetched with (see [`Number::canonical_query()`]).
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Number {
-    #[serde(deserialize_with = "deserialize_id")]
+    #[serde(
+        alias = "number_id",
+        deserialize_with = "deserialize_id",
+        serialize_with = "serialize_bigint_to_string"
+    )]
     id: BigInt,
+    #[serde(alias = "state")]
     status: NumberStatus,
+    #[serde(alias = "factorization", deserialize_with = "deserialize_sorted")]
     factors: Vec<Factor>,
+    #[serde(skip)]
+    canonical_query: Option<String>,
 }
 
 impl Number {
     /// Returns the FactorDB ID as a [`BigInt`].
+    ///
+    /// Numbers derived locally rather than looked up, e.g. by [`Self::without_factor()`], have an ID of -1, which
+    /// doesn't belong to any FactorDB entry.
     pub fn id(&self) -> &BigInt {
         &self.id
     }
@@ -34,11 +59,46 @@ impl Number {
         &self.status
     }
 
-    /// Returns a vector of [`Factor`].
+    /// Returns a vector of [`Factor`], sorted by base in ascending order.
     pub fn factors(&self) -> &Vec<Factor> {
         &self.factors
     }
 
+    /// Checks whether the factors are sorted by base in ascending order.
+    ///
+    /// Factors are sorted when a response is deserialized, so this should always be `true`. Methods of this struct rely
+    /// on that order, so this is mostly useful as a sanity check, e.g. in `debug_assert!`.
+    pub fn factors_are_sorted(&self) -> bool {
+        self.factors.windows(2).all(|w| w[0].base() <= w[1].base())
+    }
+
+    /// Returns the number itself, i.e. the product of all factors raised to their exponents.
+    ///
+    /// FactorDB always returns factors which multiply to the number, even if it isn't fully factored yet (in which
+    /// case one of the factors is the remaining composite cofactor), so this works regardless of [`Self::status()`].
+    /// Since factors are sorted by base, that cofactor isn't necessarily the last one.
+    pub fn value(&self) -> BigInt {
+        match self.status {
+            NumberStatus::Zero => BigInt::from(0),
+            NumberStatus::Unit => BigInt::from(1),
+            _ => self.factors.iter().map(|f| f.pow()).product(),
+        }
+    }
+
+    /// Returns the query FactorDB resolved the request to, if it redirected the request to a different one.
+    ///
+    /// FactorDB may canonicalize a query such as `2^10` by redirecting it to the entry for `1024`, in which case this
+    /// returns `Some("1024")`. This is `None` if the query was answered as-is, as well as for numbers which weren't
+    /// fetched by a client (e.g. deserialized from JSON).
+    pub fn canonical_query(&self) -> Option<&str> {
+        self.canonical_query.as_deref()
+    }
+
+    #[cfg(feature = "client")]
+    pub(crate) fn set_canonical_query(&mut self, query: String) {
+        self.canonical_query = Some(query);
+    }
+
     /// Returns `true` if the number may be prime.
     ///
     /// Use [`Self::is_definitely_prime()`] to check if the number have been confirmed to be prime.
@@ -54,11 +114,414 @@ impl Number {
         self.status == NumberStatus::DefinitelyPrime
     }
 
+    /// Returns `true` if the number is known to be composite, whether or not it has been factored.
+    pub fn is_composite(&self) -> bool {
+        matches!(
+            self.status,
+            NumberStatus::NoFactorsKnown | NumberStatus::FactorsKnown | NumberStatus::FullyFactored
+        )
+    }
+
+    /// Returns `true` if the number is composite and all of its prime factors are known.
+    pub fn is_fully_factored(&self) -> bool {
+        self.status == NumberStatus::FullyFactored
+    }
+
+    /// Returns `true` if the number is 1.
+    pub fn is_unit(&self) -> bool {
+        self.status == NumberStatus::Unit
+    }
+
+    /// Returns `true` if the number is 0.
+    pub fn is_zero(&self) -> bool {
+        self.status == NumberStatus::Zero
+    }
+
+    /// Returns `true` if FactorDB can't find any more factors for this number.
+    #[cfg_attr(not(feature = "client"), allow(dead_code))]
+    pub(crate) fn is_factorization_complete(&self) -> bool {
+        matches!(
+            self.status,
+            NumberStatus::FullyFactored
+                | NumberStatus::DefinitelyPrime
+                | NumberStatus::ProbablyPrime
+                | NumberStatus::Unit
+                | NumberStatus::Zero
+        )
+    }
+
+    /// Returns `true` if FactorDB hasn't made any progress factoring this number yet, i.e. it's a composite whose only
+    /// known factor is the number itself.
+    ///
+    /// This is the state of a freshly submitted composite, so bulk submitters can use it to schedule a later poll.
+    pub fn is_unfactored(&self) -> bool {
+        self.status == NumberStatus::NoFactorsKnown
+            && self.factors.len() == 1
+            && self.factors[0].exponent() == &BigInt::from(1)
+    }
+
+    /// Returns the Möbius function of this number: 0 if it has a squared prime factor, otherwise 1 if it has an even
+    /// number of prime factors and -1 if it has an odd number of them.
+    ///
+    /// The result is only meaningful if the number is fully factored (or prime), since a composite factor would be
+    /// counted as a single prime. For 0, this returns 0.
+    pub fn mobius(&self) -> i8 {
+        if self.status == NumberStatus::Zero {
+            return 0;
+        }
+        let one = BigInt::from(1);
+        let mut result = 1;
+        for factor in self.prime_factors() {
+            if factor.exponent() > &one {
+                return 0;
+            }
+            result = -result;
+        }
+        result
+    }
+
+    /// Returns the prime factors of this number which are congruent to `residue` modulo `modulus`. 0 and 1 have no
+    /// prime factors, so the result is empty for them.
+    ///
+    /// For example, the primes congruent to 1 mod 4 are exactly the ones which are a sum of two squares.
+    ///
+    /// # Panics
+    /// Panics if `modulus` is zero.
+    pub fn primes_congruent(&self, modulus: &BigInt, residue: &BigInt) -> Vec<&BigInt> {
+        let residue = residue.mod_floor(modulus);
+        self.prime_factors()
+            .map(|f| f.base())
+            .filter(|base| base.mod_floor(modulus) == residue)
+            .collect()
+    }
+
+    /// Returns `true` if this number can be written as a sum of two squares.
+    ///
+    /// By the sum of two squares theorem, this is the case exactly when every prime factor congruent to 3 mod 4 has
+    /// an even exponent.
+    pub fn is_sum_of_two_squares(&self) -> bool {
+        let four = BigInt::from(4);
+        let three = BigInt::from(3);
+        self.prime_factors()
+            .all(|f| f.base().mod_floor(&four) != three || f.exponent().is_even())
+    }
+
+    /// Returns `r2(n)`, the number of ways this number can be written as `a^2 + b^2`, counting signs and order (so
+    /// `5 = 1^2 + 2^2` has 8 representations).
+    ///
+    /// Computed as 4 times the product of `e + 1` over the prime factors congruent to 1 mod 4, or 0 if
+    /// [`Self::is_sum_of_two_squares()`] is `false`.
+    pub fn sum_of_two_squares_count(&self) -> BigInt {
+        if self.status == NumberStatus::Zero {
+            return BigInt::from(1);
+        }
+        if !self.is_sum_of_two_squares() {
+            return BigInt::from(0);
+        }
+        let four = BigInt::from(4);
+        let one = BigInt::from(1);
+        let product: BigInt = self
+            .prime_factors()
+            .filter(|f| f.base().mod_floor(&four) == one)
+            .map(|f| f.exponent() + 1)
+            .product();
+        product * four
+    }
+
+    /// Returns `true` if this number is a perfect square, i.e. all of its exponents are even. 0 and 1 are squares.
+    pub fn is_square(&self) -> bool {
+        self.status == NumberStatus::Zero || self.prime_factors().all(|f| f.exponent().is_even())
+    }
+
+    /// Returns `true` if this number is pronic, i.e. the product of two consecutive integers `k(k + 1)`.
+    pub fn is_pronic(&self) -> bool {
+        let value = self.value();
+        let k = value.sqrt();
+        &k * (&k + 1) == value
+    }
+
+    /// Returns `true` if this number is triangular, i.e. of the form `k(k + 1) / 2`.
+    pub fn is_triangular(&self) -> bool {
+        // n is triangular iff 8n + 1 is a perfect square
+        let discriminant: BigInt = self.value() * 8 + 1;
+        let root = discriminant.sqrt();
+        &root * &root == discriminant
+    }
+
+    /// Splits this number into `(s, p)` with `n = s * p`, where the squarefree part `s` is the product of the primes
+    /// with exponent 1 and the powerful part `p` is the product of the prime powers with exponent 2 or higher.
+    ///
+    /// For example, 360 = 2^3 * 3^2 * 5 splits into `(5, 72)`. Returns `(0, 1)` for 0.
+    pub fn squarefree_powerful_decomposition(&self) -> (BigInt, BigInt) {
+        if self.status == NumberStatus::Zero {
+            return (BigInt::from(0), BigInt::from(1));
+        }
+        let one = BigInt::from(1);
+        let (squarefree, powerful): (Vec<_>, Vec<_>) =
+            self.prime_factors().partition(|f| f.exponent() == &one);
+        (
+            squarefree.iter().map(|f| f.base()).product(),
+            powerful.iter().map(|f| f.pow()).product(),
+        )
+    }
+
+    /// Returns the gcd of the exponents of all factors, i.e. the largest `k` for which this number is a perfect `k`-th
+    /// power.
+    ///
+    /// Since 0 and 1 are `k`-th powers for every `k`, this returns 0 for them.
+    pub fn exponent_gcd(&self) -> BigInt {
+        self.prime_factors()
+            .fold(BigInt::from(0), |gcd, f| gcd.gcd(f.exponent()))
+    }
+
+    /// Returns `true` if this number is a harmonic divisor number (also known as an Ore number), i.e. the harmonic mean
+    /// of its divisors `n * tau(n) / sigma(n)` is an integer, like for 6 and 28.
+    ///
+    /// Returns `false` unless [`Self::divisor_count()`] and [`Self::sum_of_divisors()`] are known exactly.
+    pub fn is_harmonic_divisor_number(&self) -> bool {
+        match (self.divisor_count(), self.sum_of_divisors()) {
+            (Some(tau), Some(sigma)) => (self.value() * tau).is_multiple_of(&sigma),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this number is refactorable (also known as a tau number), i.e. divisible by its number of
+    /// divisors.
+    pub fn is_refactorable(&self) -> bool {
+        self.status != NumberStatus::Zero && self.value().is_multiple_of(&self.tau())
+    }
+
+    /// Returns `true` if this number is a Lucas-Carmichael number, i.e. an odd, squarefree composite where `p + 1`
+    /// divides `n + 1` for every prime factor `p`.
+    pub fn is_lucas_carmichael(&self) -> bool {
+        let value = self.value();
+        let factors: Vec<_> = self.prime_factors().collect();
+        let one = BigInt::from(1);
+        factors.len() >= 2
+            && value.is_odd()
+            && factors.iter().all(|f| f.exponent() == &one)
+            && factors
+                .iter()
+                .all(|f| (&value + 1u32).is_multiple_of(&(f.base() + 1u32)))
+    }
+
+    /// Returns `true` if this number is a repunit in `base`, i.e. `(base^k - 1) / (base - 1)` for some `k >= 1`.
+    ///
+    /// See [`Self::repunit_length()`] for the number of digits.
+    pub fn is_repunit(&self, base: u32) -> bool {
+        self.repunit_length(base).is_some()
+    }
+
+    /// Returns the number of digits `k` if this number is a repunit in `base` (e.g. 3 for 111 in base 10), or `None`
+    /// if it isn't one or `base` is less than 2.
+    pub fn repunit_length(&self, base: u32) -> Option<usize> {
+        if base < 2 {
+            return None;
+        }
+        let mut value = self.value();
+        let mut length = 0;
+        while value > BigInt::from(0) {
+            let (quotient, digit) = value.div_rem(&BigInt::from(base));
+            if digit != BigInt::from(1) {
+                return None;
+            }
+            value = quotient;
+            length += 1;
+        }
+        (length > 0).then_some(length)
+    }
+
+    /// Returns the abundancy index `sigma(n) / n` of this number, where `sigma(n)` is the sum of its divisors.
+    ///
+    /// Perfect numbers have an abundancy index of exactly 2, abundant numbers above 2 and deficient numbers below it.
+    /// Returns NaN for 0.
+    pub fn abundancy_index(&self) -> f64 {
+        if self.status == NumberStatus::Zero {
+            return f64::NAN;
+        }
+        // go through logarithms since both values may be too large for a f64
+        (log2(&self.sigma()) - log2(&self.value())).exp2()
+    }
+
+    /// Returns `true` if this number has an odd number of divisors, which is the case exactly for perfect squares
+    /// (including 1). Agrees with [`Self::divisor_count_mod()`], so this returns `true` for 0 as well.
+    pub fn divisor_count_is_odd(&self) -> bool {
+        self.tau().is_odd()
+    }
+
+    /// Returns the number of divisors modulo `m`, without computing the (possibly huge) number of divisors itself.
+    ///
+    /// For example, the number of divisors is odd (i.e. this returns 1 for `m = 2`) exactly for perfect squares. Like
+    /// [`Self::divisor_count()`], this is only correct if the number is fully factored.
+    ///
+    /// # Panics
+    /// Panics if `m` is 0.
+    pub fn divisor_count_mod(&self, m: u64) -> u64 {
+        assert_ne!(m, 0, "modulus must not be 0");
+        let m_big = BigInt::from(m);
+        self.prime_factors().fold(1 % m, |count, f| {
+            let term: BigInt = (f.exponent() + 1) % &m_big;
+            let term = term.to_u64().unwrap_or(0);
+            ((u128::from(count) * u128::from(term)) % u128::from(m)) as u64
+        })
+    }
+
+    /// Removes all powers of `prime` from this number, returning the remaining cofactor along with the removed prime
+    /// power (1 if `prime` isn't a factor).
+    ///
+    /// The cofactor isn't looked up in FactorDB, so the returned [`Number`] has an ID of -1 instead of one of a FactorDB
+    /// entry. Its status is derived from what's left: [`NumberStatus::Unit`] if no factors are left, and if a single
+    /// prime of a fully factored number is left, [`NumberStatus::DefinitelyPrime`] if it fits in a [`u64`] or
+    /// [`NumberStatus::ProbablyPrime`] otherwise, as FactorDB's response doesn't say whether larger factors are proven.
+    /// Otherwise it keeps the status of this number.
+    pub fn without_factor(&self, prime: &BigInt) -> (Number, BigInt) {
+        let mut removed = BigInt::from(1);
+        let mut factors = Vec::with_capacity(self.factors.len());
+        for factor in &self.factors {
+            if factor.base() == prime {
+                removed = factor.pow();
+            } else {
+                factors.push(factor.clone());
+            }
+        }
+        let status = match factors.as_slice() {
+            [] => {
+                factors.push(Factor::new(BigInt::from(1), BigInt::from(1)));
+                NumberStatus::Unit
+            }
+            [factor]
+                if self.status == NumberStatus::FullyFactored
+                    && factor.exponent() == &BigInt::from(1) =>
+            {
+                if factor.base().to_u64().is_some() {
+                    NumberStatus::DefinitelyPrime
+                } else {
+                    NumberStatus::ProbablyPrime
+                }
+            }
+            _ => self.status.clone(),
+        };
+        let number = Number {
+            id: BigInt::from(-1),
+            status,
+            factors,
+            canonical_query: None,
+        };
+        (number, removed)
+    }
+
+    /// Returns the edges of a graph showing how this number decomposes: one from the number to each of its prime powers
+    /// `p^e`, and one from each prime power to `p` unless `e` is 1.
+    ///
+    /// For 12, this is `[(12, 4), (4, 2), (12, 3)]`. Edges are `(from, to)` pairs, ordered by factor. There are no
+    /// self-loops, so a prime power `p^e` only has the edge `(p^e, p)` and a prime, 0 and 1 have no edges at all.
+    pub fn factor_graph_edges(&self) -> Vec<(BigInt, BigInt)> {
+        let value = self.value();
+        let mut edges = vec![];
+        for factor in self.prime_factors() {
+            let power = factor.pow();
+            if power != value {
+                edges.push((value.clone(), power.clone()));
+            }
+            if &power != factor.base() {
+                edges.push((power, factor.base().clone()));
+            }
+        }
+        edges
+    }
+
+    /// Returns the factors as `(base, exponent)` pairs of [`u64`], for use with libraries working on machine-sized
+    /// integers.
+    ///
+    /// # Errors
+    /// Returns [`FactorDbError::Overflow`] with the offending value if a base or exponent doesn't fit in a [`u64`].
+    pub fn factor_pairs_u64(&self) -> Result<Vec<(u64, u64)>, FactorDbError> {
+        let to_u64 = |n: &BigInt| n.to_u64().ok_or_else(|| FactorDbError::Overflow(n.clone()));
+        self.factors
+            .iter()
+            .map(|f| Ok((to_u64(f.base())?, to_u64(f.exponent())?)))
+            .collect()
+    }
+
+    /// Returns the factors of this number which aren't in `previous`, an earlier snapshot of the same number, e.g. to
+    /// highlight what FactorDB found in between.
+    ///
+    /// Factors are compared by both base and exponent, so a factor whose exponent changed counts as new.
+    pub fn new_factors_since(&self, previous: &Number) -> Vec<Factor> {
+        self.factors
+            .iter()
+            .filter(|f| !previous.factors.contains(f))
+            .cloned()
+            .collect()
+    }
+
+    /// Formats the factors as a PARI/GP factorization matrix with one `base, exponent` row per factor, e.g.
+    /// `[2, 3; 3, 2; 5, 1]` for 360, like PARI's own `factor()` returns. Also like PARI, 1 is the empty matrix `[;]`
+    /// and 0 is `[0, 1]`.
+    pub fn to_pari_matrix(&self) -> String {
+        if self.status == NumberStatus::Zero {
+            return "[0, 1]".to_string();
+        }
+        let rows: Vec<String> = self
+            .prime_factors()
+            .map(|f| format!("{}, {}", f.base(), f.exponent()))
+            .collect();
+        if rows.is_empty() {
+            return "[;]".to_string();
+        }
+        format!("[{}]", rows.join("; "))
+    }
+
+    /// Returns each factor in exponent form (like the alternate [`Display`] of [`Factor`]), with bases longer than
+    /// `max_digits` abbreviated to their first and last `max_digits / 2` digits, e.g.
+    /// `17014...05727 (39 digits)`.
+    pub fn factors_abbreviated(&self, max_digits: usize) -> Vec<String> {
+        self.factors
+            .iter()
+            .map(|f| {
+                let digits = f.base().to_string();
+                let base = if digits.len() > max_digits {
+                    let k = max_digits / 2;
+                    format!(
+                        "{}...{} ({} digits)",
+                        &digits[..k],
+                        &digits[digits.len() - k..],
+                        digits.len()
+                    )
+                } else {
+                    digits
+                };
+                if f.exponent() == &BigInt::from(1) {
+                    base
+                } else {
+                    format!("{}^{}", base, f.exponent())
+                }
+            })
+            .collect()
+    }
+
     /// Returns a vector of unique factors of this number.
     pub fn unique_factors(&self) -> Vec<&BigInt> {
         self.factors.iter().map(|f| f.base()).collect()
     }
 
+    /// Returns the number of prime factors of this number counted with multiplicity (`Ω(n)`), i.e. the sum of all
+    /// exponents, e.g. 3 for 12 = 2^2 * 3.
+    ///
+    /// This is only meaningful if the number is fully factored, as a composite factor of a partial factorization counts
+    /// as one. 0 and 1 have no prime factors, so this returns 0 for them even though FactorDB lists them as a factor of
+    /// themselves.
+    pub fn factor_count(&self) -> BigInt {
+        self.prime_factors().map(|f| f.exponent()).sum()
+    }
+
+    /// Returns the number of distinct prime factors of this number (`ω(n)`), e.g. 2 for 12 = 2^2 * 3.
+    ///
+    /// Like [`Self::factor_count()`], this is only meaningful if the number is fully factored, and returns 0 for 0 and 1.
+    pub fn distinct_factor_count(&self) -> usize {
+        self.prime_factors().count()
+    }
+
     /// Converts `self` to a vector of unique factors of this number in ascending order.
     pub fn into_unique_factors(self) -> Vec<BigInt> {
         let mut factors: Vec<BigInt> = self
@@ -76,10 +539,369 @@ impl Number {
         factors.sort_unstable();
         factors
     }
+
+    /// Converts `self` to a vector of `(base, exponent)` pairs in ascending order of base, e.g. `[(2, 3), (3, 2), (5, 1)]`
+    /// for 360.
+    ///
+    /// Exponents which don't fit in a [`u32`] saturate to [`u32::MAX`]. Like [`Self::factors()`], 0 and 1 are a single
+    /// factor of themselves, and the last base isn't necessarily prime unless the number is fully factored.
+    pub fn into_prime_factorization(self) -> Vec<(BigInt, u32)> {
+        self.factors
+            .into_iter()
+            .map(|f| {
+                let (base, exponent) = f.into();
+                (base, exponent.to_u32().unwrap_or(u32::MAX))
+            })
+            .collect()
+    }
+
+    /// Same as [`Self::unique_factors()`], but as [`BigUint`] since factors are never negative.
+    pub fn unique_factors_uint(&self) -> Vec<BigUint> {
+        self.factors.iter().map(|f| to_biguint(f.base())).collect()
+    }
+
+    /// Same as [`Self::into_factors_flattened()`], but as [`BigUint`] since factors are never negative.
+    pub fn into_factors_flattened_uint(self) -> Vec<BigUint> {
+        self.into_factors_flattened()
+            .iter()
+            .map(to_biguint)
+            .collect()
+    }
+
+    /// Returns an estimate of `log2` of this number, computed as the sum of `exponent * log2(base)` over its factors.
+    ///
+    /// This is much cheaper than reconstructing the number itself, making it useful for comparing or sorting numbers
+    /// by magnitude. Returns negative infinity for 0.
+    pub fn log2_estimate(&self) -> f64 {
+        if self.status == NumberStatus::Zero {
+            return f64::NEG_INFINITY;
+        }
+        self.factors
+            .iter()
+            .map(|f| f.exponent().to_f64().unwrap_or(f64::NAN) * log2(f.base()))
+            .sum()
+    }
+
+    /// Returns the number of bits needed to represent this number, e.g. 11 for 1024. Returns 0 for 0.
+    ///
+    /// This is exact, but reconstructs the number from its factors with [`Self::value()`] to do so, which can be
+    /// expensive for numbers with thousands of digits. Use [`Self::value_bit_length_estimate()`] if a result which may
+    /// be off by one is good enough.
+    pub fn value_bit_length(&self) -> u64 {
+        self.value().bits()
+    }
+
+    /// Estimates [`Self::value_bit_length()`] from [`Self::log2_estimate()`] without reconstructing the number.
+    ///
+    /// Due to floating point rounding, this may be off by one for numbers very close to a power of 2.
+    pub fn value_bit_length_estimate(&self) -> u64 {
+        if self.status == NumberStatus::Zero {
+            return 0;
+        }
+        self.log2_estimate().floor() as u64 + 1
+    }
+
+    /// Returns the Shannon entropy (in bits) of the distribution of exponents across the prime factors.
+    ///
+    /// With `E` being the sum of all exponents `e_i`, this is `-sum((e_i / E) * log2(e_i / E))`. A prime power has an
+    /// entropy of 0, while a product of `k` distinct primes has the maximum entropy of `log2(k)`. Returns 0 for 0 and 1.
+    pub fn factor_entropy(&self) -> f64 {
+        let exponents: Vec<f64> = self
+            .prime_factors()
+            .map(|f| f.exponent().to_f64().unwrap_or(f64::NAN))
+            .collect();
+        let total: f64 = exponents.iter().sum();
+        exponents
+            .iter()
+            .map(|e| e / total)
+            .map(|p| p * p.recip().log2())
+            .sum()
+    }
+
+    /// Returns the number of ways to write this number as a product `a * b` with `a <= b`.
+    ///
+    /// Every divisor `a <= sqrt(n)` gives one such pair, so this is half the number of divisors, rounded up to count
+    /// `sqrt(n) * sqrt(n)` for perfect squares.
+    pub fn unordered_factor_pair_count(&self) -> BigInt {
+        (self.tau() + 1) / 2
+    }
+
+    /// Returns the number of multiplicative partitions of this number, i.e. the number of ways to write it as an
+    /// unordered product of factors greater than 1, e.g. 4 for 12 (`12`, `2 * 6`, `3 * 4` and `2 * 2 * 3`).
+    ///
+    /// The count only depends on the exponents of the factors. Each factor of a product corresponds to a nonzero
+    /// exponent vector `v <= e`, so this counts the partitions of the exponent vector `e` into such vectors, which takes
+    /// time quadratic in the number of divisors. Returns 1 for 0 and 1, counting the empty product. Like
+    /// [`Self::euler_totient()`], this is only correct if the number is fully factored.
+    ///
+    /// # Panics
+    /// Panics if the number has more than [`usize::MAX`] divisors.
+    pub fn multiplicative_partition_count(&self) -> BigInt {
+        let limits: Vec<usize> = self
+            .prime_factors()
+            .map(|f| f.exponent().to_usize().expect("exponent too large"))
+            .collect();
+        let size = limits
+            .iter()
+            .try_fold(1usize, |size, e| size.checked_mul(e + 1))
+            .expect("too many divisors");
+        // exponent vectors of all divisors, indexed in mixed radix so that `index(w - v) = index(w) - index(v)`
+        let vectors: Vec<Vec<usize>> = (0..size)
+            .map(|mut index| {
+                limits
+                    .iter()
+                    .map(|e| {
+                        let digit = index % (e + 1);
+                        index /= e + 1;
+                        digit
+                    })
+                    .collect()
+            })
+            .collect();
+        // counts[w] is the number of partitions of w into the vectors considered so far
+        let mut counts = vec![BigInt::from(0); size];
+        counts[0] = BigInt::from(1);
+        for (v, part) in vectors.iter().enumerate().skip(1) {
+            for (w, whole) in vectors.iter().enumerate().skip(v) {
+                if whole.iter().zip(part).all(|(a, b)| a >= b) {
+                    let count = counts[w - v].clone();
+                    counts[w] += count;
+                }
+            }
+        }
+        counts.pop().unwrap_or_default()
+    }
+
+    /// Returns the number of divisors of this number (the tau function), computed as the product of `exponent + 1`
+    /// over all factors.
+    ///
+    /// Returns `None` unless the number is fully factored, proven prime or 1, as the count would be wrong for a partial
+    /// factorization (and 0 has infinitely many divisors).
+    pub fn divisor_count(&self) -> Option<BigInt> {
+        match self.status {
+            NumberStatus::FullyFactored | NumberStatus::DefinitelyPrime | NumberStatus::Unit => {
+                Some(self.tau())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the sum of all divisors of this number (the sigma function), computed as the product of
+    /// `(p^(e+1) - 1) / (p - 1)` over all factors `p^e`.
+    ///
+    /// Like [`Self::divisor_count()`], returns `None` unless the number is fully factored, proven prime or 1. The
+    /// number is perfect if the sum is twice the number, and abundant if it's more than that.
+    pub fn sum_of_divisors(&self) -> Option<BigInt> {
+        self.divisor_count().map(|_| self.sigma())
+    }
+
+    /// Returns the number of divisors of this number, or an error if it's larger than `max`.
+    ///
+    /// The count is the product of `exponent + 1` over all factors, so it only ever needs the exponents and stays cheap
+    /// even for numbers with astronomically many divisors, where enumerating the divisors themselves would run out of
+    /// memory. The product stops being computed as soon as it exceeds `max`.
+    ///
+    /// # Errors
+    /// Returns a [`TooManyDivisorsError`] if the number has more than `max` divisors.
+    pub fn divisor_count_checked(&self, max: &BigInt) -> Result<BigInt, TooManyDivisorsError> {
+        let mut count = BigInt::from(1);
+        for factor in self.prime_factors() {
+            count *= factor.exponent() + 1;
+            if &count > max {
+                return Err(TooManyDivisorsError { max: max.clone() });
+            }
+        }
+        Ok(count)
+    }
+
+    /// Returns all divisors of this number in ascending order, or an error if there are more than `max` of them.
+    ///
+    /// The number of divisors grows quickly with the number of factors, so this checks the count with
+    /// [`Self::divisor_count_checked()`] before enumerating anything.
+    ///
+    /// # Errors
+    /// Returns a [`TooManyDivisorsError`] if the number has more than `max` divisors.
+    pub fn divisors_capped(&self, max: usize) -> Result<Vec<BigInt>, TooManyDivisorsError> {
+        let count = self.divisor_count_checked(&BigInt::from(max))?;
+        let mut divisors = Vec::with_capacity(count.to_usize().unwrap_or(max));
+        divisors.push(BigInt::from(1));
+        for factor in self.prime_factors() {
+            let mut powers = divisors.clone();
+            for _ in factor.iter() {
+                powers = powers.into_iter().map(|d| d * factor.base()).collect();
+                divisors.extend(powers.iter().cloned());
+            }
+        }
+        divisors.sort();
+        Ok(divisors)
+    }
+
+    /// Returns all divisors of this number in ascending order (including 1 and the number itself), e.g.
+    /// `[1, 2, 3, 4, 6, 12]` for 12. Reverse the result for descending order.
+    ///
+    /// Like [`Self::divisor_count()`], returns `None` unless the number is fully factored, proven prime or 1, since
+    /// divisors would be missing for a partial factorization. Numbers with many factors have a huge number of divisors,
+    /// see [`Self::divisors_capped()`] to guard against that.
+    pub fn divisors(&self) -> Option<Vec<BigInt>> {
+        self.divisor_count()?;
+        Some(self.divisors_iter().collect())
+    }
+
+    /// Returns an iterator over all divisors of this number in ascending order.
+    ///
+    /// Divisors are generated lazily, so taking the first few stays cheap even if the number has far too many divisors
+    /// to fit in memory. See [`Self::divisors_capped()`] to collect all of them at once.
+    pub fn divisors_iter(&self) -> impl Iterator<Item = BigInt> {
+        let (primes, limits): (Vec<_>, Vec<_>) = self
+            .prime_factors()
+            .map(|f| (f.base().clone(), f.exponent().to_u64().unwrap_or(u64::MAX)))
+            .unzip();
+        let heap = BinaryHeap::from([Reverse((BigInt::from(1), 0, vec![0; primes.len()]))]);
+        DivisorsIter {
+            primes,
+            limits,
+            heap,
+        }
+    }
+
+    /// Computes Euler's totient `φ(n)`, the number of integers from 1 to `n` which are coprime to `n`.
+    ///
+    /// This is the product of `p^(e-1) * (p - 1)` over all factors `p^e`, so it is only correct if the number is
+    /// fully factored. Returns 0 for 0 and 1 for 1.
+    pub fn euler_totient(&self) -> BigInt {
+        if self.status == NumberStatus::Zero {
+            return BigInt::from(0);
+        }
+        self.prime_factors()
+            .map(|f| {
+                let (p, e) = f.as_pair();
+                Factor::new(p.clone(), e - 1).pow() * (p - 1)
+            })
+            .product()
+    }
+
+    /// Reduces `exp` modulo [`Self::euler_totient()`], so that `a^exp mod n` can be computed as `a^(exp mod φ(n)) mod n`
+    /// with a much smaller exponent. The result is never negative.
+    ///
+    /// By Euler's theorem, this only holds if `a` is coprime to `n`. If `gcd(a, n) != 1`, `a^exp mod n` generally
+    /// differs from `a^(exp mod φ(n)) mod n`. Like [`Self::euler_totient()`], this is only correct if the number is
+    /// fully factored.
+    ///
+    /// # Panics
+    /// Panics if the number is 0, as `φ(0) = 0`.
+    pub fn reduce_exponent_mod_totient(&self, exp: &BigInt) -> BigInt {
+        exp.mod_floor(&self.euler_totient())
+    }
+
+    /// Computes the Kempner/Smarandache function `S(n)`, i.e. the smallest `m` such that `n` divides `m!`.
+    ///
+    /// This is the largest `S(p^e)` over all factors `p^e`, each of which is found by a binary search over multiples of
+    /// `p` using Legendre's formula for the exponent of `p` in `m!`. By convention `S(1) = 1`; since no factorial is
+    /// divisible by 0, this returns 0 for 0.
+    pub fn smarandache(&self) -> BigInt {
+        if self.status == NumberStatus::Zero {
+            return BigInt::from(0);
+        }
+        self.prime_factors()
+            .map(|f| {
+                let (p, e) = f.as_pair();
+                // S(p^e) = k * p for the smallest k in 1..=e with v_p((k * p)!) >= e
+                let (mut low, mut high) = (BigInt::from(1), e.clone());
+                while low < high {
+                    let mid: BigInt = (&low + &high) / 2;
+                    if &legendre(&(&mid * p), p) >= e {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                low * p
+            })
+            .max()
+            .unwrap_or_else(|| BigInt::from(1))
+    }
+
+    /// Factors other than the trivial factor of 1 (which FactorDB returns for 1 itself).
+    fn prime_factors(&self) -> impl Iterator<Item = &Factor> {
+        let one = BigInt::from(1);
+        self.factors.iter().filter(move |f| f.base() > &one)
+    }
+
+    /// The fields identifying the FactorDB entry, i.e. everything but how it was queried.
+    fn entry(&self) -> (&BigInt, &NumberStatus, &Vec<Factor>) {
+        (&self.id, &self.status, &self.factors)
+    }
+
+    /// Number of divisors, computed as the product of `e + 1` over all factors.
+    fn tau(&self) -> BigInt {
+        self.prime_factors().map(|f| f.exponent() + 1).product()
+    }
+
+    /// Sum of divisors, computed as the product of `(p^(e+1) - 1) / (p - 1)` over all factors.
+    pub(crate) fn sigma(&self) -> BigInt {
+        let one = BigInt::from(1);
+        self.prime_factors()
+            .map(|f| (f.pow() * f.base() - &one) / (f.base() - &one))
+            .product()
+    }
+
+    /// `log2(sigma(n) / n^(1 + epsilon))`, the quantity maximised by colossally abundant numbers.
+    ///
+    /// Returns [`FactorDbError::Overflow`] with the exponent if a factor's exponent doesn't fit in a [`u32`].
+    #[cfg_attr(not(feature = "client"), allow(dead_code))]
+    pub(crate) fn colossal_abundance_score(&self, epsilon: f64) -> Result<f64, FactorDbError> {
+        let (mut sigma, mut value) = (BigInt::from(1), BigInt::from(1));
+        for f in self.prime_factors() {
+            let power = f
+                .checked_pow()
+                .ok_or_else(|| FactorDbError::Overflow(f.exponent().clone()))?;
+            sigma *= (&power * f.base() - 1) / (f.base() - 1);
+            value *= power;
+        }
+        Ok(log2(&sigma) - (1.0 + epsilon) * log2(&value))
+    }
 }
 
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.entry() == other.entry()
+    }
+}
+
+impl Eq for Number {}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.entry().cmp(&other.entry())
+    }
+}
+
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entry().hash(state);
+    }
+}
+
+/// Formats the prime factors repeated by their exponents, separated by spaces (e.g. `2 2 2 3 3 5`).
+///
+/// The alternate form (`{:#}`) prints the number followed by its factorization in exponent form instead (e.g.
+/// `360 = 2^3 * 3^2 * 5`).
 impl Display for Number {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            let factor_strings: Vec<String> = self
+                .factors
+                .iter()
+                .map(|factor| format!("{:#}", factor))
+                .collect();
+            return write!(f, "{} = {}", self.value(), factor_strings.join(" * "));
+        }
         let factor_strings: Vec<String> = self
             .clone()
             .into_factors_flattened()
@@ -90,6 +912,187 @@ impl Display for Number {
     }
 }
 
+/// Iterator returned by [`Number::divisors_iter()`].
+struct DivisorsIter {
+    primes: Vec<BigInt>,
+    limits: Vec<u64>,
+    /// Divisors to yield next, along with the index of their largest prime factor and their exponents. Every divisor
+    /// is only ever reached by multiplying its prime factors in ascending order, so none is pushed twice.
+    heap: BinaryHeap<Reverse<(BigInt, usize, Vec<u64>)>>,
+}
+
+impl Iterator for DivisorsIter {
+    type Item = BigInt;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((divisor, last, exponents)) = self.heap.pop()?;
+        for i in last..self.primes.len() {
+            if exponents[i] < self.limits[i] {
+                let mut next_exponents = exponents.clone();
+                next_exponents[i] += 1;
+                self.heap
+                    .push(Reverse((&divisor * &self.primes[i], i, next_exponents)));
+            }
+        }
+        Some(divisor)
+    }
+}
+
+/// Converts a factor, which FactorDB never returns negative, to a [`BigUint`].
+fn to_biguint(factor: &BigInt) -> BigUint {
+    factor
+        .to_biguint()
+        .expect("FactorDB factors are never negative")
+}
+
+/// Exponent of the prime `p` in `m!`, by Legendre's formula.
+fn legendre(m: &BigInt, p: &BigInt) -> BigInt {
+    let mut exponent = BigInt::from(0);
+    let mut m = m / p;
+    while m > BigInt::from(0) {
+        exponent += &m;
+        m /= p;
+    }
+    exponent
+}
+
+/// Serializes `numbers` into a single JSON array, in the same format as FactorDB's responses.
+///
+/// Use [`numbers_from_json_array()`] to parse it back.
+///
+/// # Errors
+/// Returns [`FactorDbError::ParseError`] if a number can't be serialized, i.e. if it has an exponent which doesn't
+/// fit in a [`u64`].
+pub fn numbers_to_json_array(numbers: &[Number]) -> Result<String, FactorDbError> {
+    Ok(serde_json::to_string(numbers)?)
+}
+
+/// Parses a JSON array of numbers, as written by [`numbers_to_json_array()`].
+///
+/// # Errors
+/// Returns [`FactorDbError::ParseError`] if `json` isn't an array of numbers in FactorDB's format.
+pub fn numbers_from_json_array(json: &str) -> Result<Vec<Number>, FactorDbError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Parses numbers one at a time from `reader`, which contains either a JSON array of numbers (like a file written from
+/// [`numbers_to_json_array()`]) or one number per line (JSON Lines), in FactorDB's format.
+///
+/// Unlike [`numbers_from_json_array()`], only one number is held in memory at a time, so this can go through dumps
+/// far larger than the available memory. `reader` doesn't need to be buffered.
+///
+/// The iterator ends after the first error, which is a [`FactorDbError::ParseError`] for both invalid JSON and I/O
+/// errors.
+pub fn parse_json_stream<R: Read>(
+    reader: R,
+) -> impl Iterator<Item = Result<Number, FactorDbError>> {
+    JsonStream {
+        reader: BufReader::new(reader),
+        state: JsonStreamState::Start,
+    }
+}
+
+/// Iterator returned by [`parse_json_stream()`].
+struct JsonStream<R> {
+    reader: BufReader<R>,
+    state: JsonStreamState,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum JsonStreamState {
+    /// Nothing has been read yet, so it's not known whether this is an array
+    Start,
+    /// Inside an array, `first` being whether no element has been read yet
+    Array { first: bool },
+    /// Reading whitespace-separated values
+    Lines,
+    /// The end of the input or an error has been reached
+    Done,
+}
+
+impl<R: Read> JsonStream<R> {
+    /// Returns the next byte which isn't whitespace without consuming it, or `None` at the end of the input.
+    fn peek(&mut self) -> std::io::Result<Option<u8>> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(i) => {
+                    let byte = buf[i];
+                    self.reader.consume(i);
+                    return Ok(Some(byte));
+                }
+                None if buf.is_empty() => return Ok(None),
+                None => {
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    fn next_number(&mut self) -> Result<Option<Number>, serde_json::Error> {
+        let mut next = self.peek().map_err(serde_json::Error::io)?;
+        match (self.state, next) {
+            (JsonStreamState::Start, Some(b'[')) => {
+                self.reader.consume(1);
+                self.state = JsonStreamState::Array { first: true };
+                next = self.peek().map_err(serde_json::Error::io)?;
+            }
+            (JsonStreamState::Start, _) => self.state = JsonStreamState::Lines,
+            _ => {}
+        }
+        match (self.state, next) {
+            (JsonStreamState::Array { .. }, Some(b']')) | (JsonStreamState::Lines, None) => {
+                return Ok(None)
+            }
+            (JsonStreamState::Array { .. }, None) => {
+                return Err(de::Error::custom("unexpected end of input inside an array"))
+            }
+            (JsonStreamState::Array { first: false }, Some(b',')) => self.reader.consume(1),
+            (JsonStreamState::Array { first: false }, Some(_)) => {
+                return Err(de::Error::custom("expected `,` or `]` between numbers"))
+            }
+            _ => {}
+        }
+        if let JsonStreamState::Array { first } = &mut self.state {
+            *first = false;
+        }
+        // serde_json reads objects byte by byte without looking past the closing brace, so the reader ends up right
+        // after the number
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut self.reader);
+        Number::deserialize(&mut deserializer).map(Some)
+    }
+}
+
+impl<R: Read> Iterator for JsonStream<R> {
+    type Item = Result<Number, FactorDbError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == JsonStreamState::Done {
+            return None;
+        }
+        let result = self.next_number().transpose();
+        if !matches!(result, Some(Ok(_))) {
+            self.state = JsonStreamState::Done;
+        }
+        result.map(|r| r.map_err(FactorDbError::from))
+    }
+}
+
+/// Error returned when a number has more divisors than the caller is willing to handle.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Number has more than {max} divisors")]
+pub struct TooManyDivisorsError {
+    max: BigInt,
+}
+
+impl TooManyDivisorsError {
+    /// Returns the maximum number of divisors which was exceeded.
+    pub fn max(&self) -> &BigInt {
+        &self.max
+    }
+}
+
 /// The status of a number in FactorDB.
 ///
 /// More information: <http://factordb.com/status.html>
@@ -108,6 +1111,9 @@ pub enum NumberStatus {
     #[serde(rename = "P")]
     DefinitelyPrime,
     /// Probably prime (Prp)
+    ///
+    /// The API only reports the status itself. Which probable-prime tests were run (and on what bases) is only shown
+    /// on the number's page on the FactorDB website, so there is no way to tell how strong the claim is from here.
     #[serde(rename = "Prp")]
     #[serde(alias = "PRP")]
     ProbablyPrime,
@@ -122,3 +1128,819 @@ pub enum NumberStatus {
     #[serde(rename = "N")]
     NotInDatabase,
 }
+
+/// Formats the status as the code FactorDB uses for it, e.g. `FF` for [`NumberStatus::FullyFactored`], which
+/// [`NumberStatus::from_str()`] parses back.
+impl Display for NumberStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let code = match self {
+            NumberStatus::NoFactorsKnown => "C",
+            NumberStatus::FactorsKnown => "CF",
+            NumberStatus::FullyFactored => "FF",
+            NumberStatus::DefinitelyPrime => "P",
+            NumberStatus::ProbablyPrime => "Prp",
+            NumberStatus::Unknown => "U",
+            NumberStatus::Unit => "Unit",
+            NumberStatus::Zero => "Zero",
+            NumberStatus::NotInDatabase => "N",
+        };
+        f.write_str(code)
+    }
+}
+
+/// Parses the code FactorDB uses for a status, the same way as when deserializing a response (so both `Prp` and
+/// `PRP` are probably prime).
+impl FromStr for NumberStatus {
+    type Err = ParseStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "C" => Ok(NumberStatus::NoFactorsKnown),
+            "CF" => Ok(NumberStatus::FactorsKnown),
+            "FF" => Ok(NumberStatus::FullyFactored),
+            "P" => Ok(NumberStatus::DefinitelyPrime),
+            "Prp" | "PRP" => Ok(NumberStatus::ProbablyPrime),
+            "U" => Ok(NumberStatus::Unknown),
+            "Unit" => Ok(NumberStatus::Unit),
+            "Zero" => Ok(NumberStatus::Zero),
+            "N" => Ok(NumberStatus::NotInDatabase),
+            _ => Err(ParseStatusError(s.to_string())),
+        }
+    }
+}
+
+/// Error returned when parsing a string which isn't a status code as a [`NumberStatus`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown status code: {0}")]
+pub struct ParseStatusError(String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    /// Builds a fully factored number out of `(base, exponent)` pairs.
+    fn number(factors: &[(u64, u64)]) -> Number {
+        Number {
+            id: BigInt::from(0),
+            status: NumberStatus::FullyFactored,
+            factors: factors
+                .iter()
+                .map(|&(b, e)| Factor::new(BigInt::from(b), BigInt::from(e)))
+                .collect(),
+            canonical_query: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let json = r#"{"id":"1100000000000210576","status":"FF","factors":[["2",2],["3",1]]}"#;
+        let number: Number = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            number.id(),
+            &"1100000000000210576".parse::<BigInt>().unwrap()
+        );
+        assert_eq!(number.status(), &NumberStatus::FullyFactored);
+        assert_eq!(number.factors(), self::number(&[(2, 2), (3, 1)]).factors());
+    }
+
+    #[test]
+    fn test_eq_ignores_canonical_query() {
+        use std::collections::HashSet;
+
+        let plain = number(&[(2, 10)]);
+        let mut redirected = plain.clone();
+        redirected.canonical_query = Some("1024".to_string());
+        assert_eq!(plain, redirected);
+        assert_eq!(plain.cmp(&redirected), Ordering::Equal);
+        assert_eq!(HashSet::from([plain, redirected]).len(), 1);
+        assert_ne!(number(&[(2, 10)]), number(&[(2, 9)]));
+    }
+
+    #[test]
+    fn test_parse_aliased_fields() {
+        let json = r#"{"number_id":"12","state":"FF","factorization":[["2",2],["3",1]]}"#;
+        let number: Number = serde_json::from_str(json).unwrap();
+        assert_eq!(number.id(), &BigInt::from(12));
+        assert_eq!(number.status(), &NumberStatus::FullyFactored);
+        assert_eq!(number.value(), BigInt::from(12));
+        assert_eq!(
+            serde_json::to_string(&number).unwrap(),
+            r#"{"id":"12","status":"FF","factors":[["2",2],["3",1]]}"#
+        );
+    }
+
+    #[test]
+    fn test_unsorted_factors() {
+        let json = r#"{"id":"90","status":"FF","factors":[["5",1],["2",1],["3",2]]}"#;
+        let number: Number = serde_json::from_str(json).unwrap();
+        assert!(number.factors_are_sorted());
+        assert_eq!(
+            number.factors(),
+            self::number(&[(2, 1), (3, 2), (5, 1)]).factors()
+        );
+        assert!(!self::number(&[(3, 1), (2, 1)]).factors_are_sorted());
+    }
+
+    #[test]
+    fn test_status_predicates() {
+        let fifteen = number(&[(3, 1), (5, 1)]);
+        let [zero, one, prime] = ["zero", "one", "prime"].map(test_utils::load_fixture);
+        let partial = test_utils::load_fixture("partial");
+        assert!(fifteen.is_composite() && fifteen.is_fully_factored());
+        assert!(partial.is_composite() && !partial.is_fully_factored());
+        for n in [&zero, &one, &prime] {
+            assert!(!n.is_composite() && !n.is_fully_factored());
+        }
+        assert!(zero.is_zero() && !zero.is_unit());
+        assert!(one.is_unit() && !one.is_zero());
+        assert!(!prime.is_unit() && !prime.is_zero() && !fifteen.is_zero());
+    }
+
+    #[test]
+    fn test_value() {
+        assert_eq!(number(&[(2, 3), (3, 2), (5, 1)]).value(), BigInt::from(360));
+        for (name, value) in [("prime", 17), ("zero", 0), ("one", 1)] {
+            assert_eq!(test_utils::load_fixture(name).value(), BigInt::from(value));
+        }
+        assert_eq!(
+            test_utils::load_fixture("partial").value(),
+            "3000000048000000189".parse::<BigInt>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_factor_count() {
+        let twelve = number(&[(2, 2), (3, 1)]);
+        assert_eq!(twelve.factor_count(), BigInt::from(3));
+        assert_eq!(twelve.distinct_factor_count(), 2);
+        let composite = test_utils::load_fixture("composite");
+        assert_eq!(composite.factor_count(), BigInt::from(6));
+        assert_eq!(composite.distinct_factor_count(), 3);
+        let prime = test_utils::load_fixture("prime");
+        assert_eq!(prime.factor_count(), BigInt::from(1));
+        assert_eq!(prime.distinct_factor_count(), 1);
+        for name in ["one", "zero"] {
+            let number = test_utils::load_fixture(name);
+            assert_eq!(number.factor_count(), BigInt::from(0));
+            assert_eq!(number.distinct_factor_count(), 0);
+        }
+    }
+
+    #[test]
+    fn test_into_prime_factorization() {
+        assert_eq!(
+            number(&[(2, 3), (3, 2), (5, 1)]).into_prime_factorization(),
+            vec![
+                (BigInt::from(2), 3),
+                (BigInt::from(3), 2),
+                (BigInt::from(5), 1)
+            ]
+        );
+        assert_eq!(
+            number(&[(2, u64::MAX)]).into_prime_factorization(),
+            vec![(BigInt::from(2), u32::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_factors_uint() {
+        let composite = test_utils::load_fixture("composite");
+        assert_eq!(
+            composite.unique_factors_uint(),
+            [2u32, 3, 5].map(BigUint::from)
+        );
+        let signed = composite.clone().into_factors_flattened();
+        let unsigned = composite.into_factors_flattened_uint();
+        assert_eq!(unsigned, [2u32, 2, 2, 3, 3, 5].map(BigUint::from));
+        assert!(signed
+            .iter()
+            .zip(&unsigned)
+            .all(|(s, u)| s.to_biguint().as_ref() == Some(u)));
+    }
+
+    #[test]
+    fn test_mobius() {
+        assert_eq!(number(&[]).mobius(), 1);
+        assert_eq!(number(&[(2, 1)]).mobius(), -1);
+        assert_eq!(number(&[(2, 1), (3, 1)]).mobius(), 1);
+        assert_eq!(number(&[(2, 2), (3, 1)]).mobius(), 0);
+    }
+
+    #[test]
+    fn test_primes_congruent() {
+        let n = number(&[(3, 1), (5, 1), (7, 1), (13, 1)]);
+        let four = BigInt::from(4);
+        assert_eq!(
+            n.primes_congruent(&four, &BigInt::from(1)),
+            vec![&BigInt::from(5), &BigInt::from(13)]
+        );
+        assert_eq!(
+            n.primes_congruent(&four, &BigInt::from(-1)),
+            vec![&BigInt::from(3), &BigInt::from(7)]
+        );
+        assert!(n.primes_congruent(&four, &BigInt::from(2)).is_empty());
+        for (name, residue) in [("one", 1), ("zero", 0)] {
+            let n = test_utils::load_fixture(name);
+            assert!(n.primes_congruent(&four, &BigInt::from(residue)).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_is_sum_of_two_squares() {
+        assert!(number(&[(3, 2), (5, 1)]).is_sum_of_two_squares());
+        assert!(!number(&[(3, 1), (7, 1)]).is_sum_of_two_squares());
+        assert!(number(&[(2, 1)]).is_sum_of_two_squares());
+    }
+
+    #[test]
+    fn test_sum_of_two_squares_count() {
+        assert_eq!(
+            number(&[(5, 2)]).sum_of_two_squares_count(),
+            BigInt::from(12)
+        );
+        assert_eq!(
+            number(&[(5, 1)]).sum_of_two_squares_count(),
+            BigInt::from(8)
+        );
+        assert_eq!(
+            number(&[(3, 1)]).sum_of_two_squares_count(),
+            BigInt::from(0)
+        );
+        assert_eq!(
+            test_utils::load_fixture("one").sum_of_two_squares_count(),
+            BigInt::from(4)
+        );
+        assert_eq!(
+            test_utils::load_fixture("zero").sum_of_two_squares_count(),
+            BigInt::from(1)
+        );
+    }
+
+    #[test]
+    fn test_shape_predicates() {
+        let twelve = number(&[(2, 2), (3, 1)]);
+        let ten = number(&[(2, 1), (5, 1)]);
+        let sixteen = number(&[(2, 4)]);
+        assert!(twelve.is_pronic());
+        assert!(!twelve.is_triangular());
+        assert!(!twelve.is_square());
+        assert!(ten.is_triangular());
+        assert!(!ten.is_pronic());
+        assert!(!ten.is_square());
+        assert!(sixteen.is_square());
+        assert!(!sixteen.is_pronic());
+        assert!(!sixteen.is_triangular());
+        for name in ["zero", "one"] {
+            assert!(test_utils::load_fixture(name).is_square(), "{}", name);
+        }
+        assert!(!test_utils::load_fixture("prime").is_square());
+    }
+
+    #[test]
+    fn test_squarefree_powerful_decomposition() {
+        assert_eq!(
+            number(&[(2, 3), (3, 2), (5, 1)]).squarefree_powerful_decomposition(),
+            (BigInt::from(5), BigInt::from(72))
+        );
+        assert_eq!(
+            number(&[(2, 1), (3, 1), (7, 1)]).squarefree_powerful_decomposition(),
+            (BigInt::from(42), BigInt::from(1))
+        );
+        assert_eq!(
+            number(&[(2, 4)]).squarefree_powerful_decomposition(),
+            (BigInt::from(1), BigInt::from(16))
+        );
+        assert_eq!(
+            test_utils::load_fixture("zero").squarefree_powerful_decomposition(),
+            (BigInt::from(0), BigInt::from(1))
+        );
+    }
+
+    #[test]
+    fn test_exponent_gcd() {
+        assert_eq!(number(&[(2, 3), (3, 2)]).exponent_gcd(), BigInt::from(1));
+        assert_eq!(number(&[(2, 6)]).exponent_gcd(), BigInt::from(6));
+        assert_eq!(number(&[(2, 4), (3, 6)]).exponent_gcd(), BigInt::from(2));
+        assert_eq!(number(&[]).exponent_gcd(), BigInt::from(0));
+    }
+
+    #[test]
+    fn test_is_harmonic_divisor_number() {
+        assert!(number(&[(2, 1), (3, 1)]).is_harmonic_divisor_number());
+        assert!(number(&[(2, 2), (7, 1)]).is_harmonic_divisor_number());
+        assert!(number(&[(2, 2), (5, 1), (7, 1)]).is_harmonic_divisor_number());
+        assert!(!number(&[(2, 2), (3, 1)]).is_harmonic_divisor_number());
+        assert!(test_utils::load_fixture("one").is_harmonic_divisor_number());
+        assert!(!test_utils::load_fixture("prime").is_harmonic_divisor_number());
+        assert!(!test_utils::load_fixture("zero").is_harmonic_divisor_number());
+        assert!(!test_utils::load_fixture("partial").is_harmonic_divisor_number());
+    }
+
+    #[test]
+    fn test_is_refactorable() {
+        assert!(number(&[(2, 2), (3, 1)]).is_refactorable());
+        assert!(!number(&[(2, 1), (5, 1)]).is_refactorable());
+        assert!(number(&[(2, 3), (3, 2)]).is_refactorable());
+        assert!(number(&[]).is_refactorable());
+        assert!(!test_utils::load_fixture("zero").is_refactorable());
+    }
+
+    #[test]
+    fn test_is_lucas_carmichael() {
+        assert!(number(&[(3, 1), (7, 1), (19, 1)]).is_lucas_carmichael());
+        assert!(number(&[(5, 1), (11, 1), (17, 1)]).is_lucas_carmichael());
+        // squarefree and odd, but 6 doesn't divide 106
+        assert!(!number(&[(5, 1), (7, 1), (3, 1)]).is_lucas_carmichael());
+        // not squarefree
+        assert!(!number(&[(3, 3), (7, 1), (19, 1)]).is_lucas_carmichael());
+        assert!(!number(&[(7, 1)]).is_lucas_carmichael());
+    }
+
+    #[test]
+    fn test_repunit() {
+        let one_one_one = number(&[(3, 1), (37, 1)]);
+        assert!(one_one_one.is_repunit(10));
+        assert_eq!(one_one_one.repunit_length(10), Some(3));
+        assert!(!number(&[(2, 4), (7, 1)]).is_repunit(10));
+        // 31 = 11111 in base 2, 111 in base 5
+        let thirty_one = number(&[(31, 1)]);
+        assert_eq!(thirty_one.repunit_length(2), Some(5));
+        assert_eq!(thirty_one.repunit_length(5), Some(3));
+        assert_eq!(thirty_one.repunit_length(1), None);
+        assert_eq!(number(&[]).repunit_length(10), Some(1));
+    }
+
+    #[test]
+    fn test_abundancy_index() {
+        assert!((number(&[(2, 1), (3, 1)]).abundancy_index() - 2.0).abs() < 1e-12);
+        assert!((number(&[(2, 2), (3, 1)]).abundancy_index() - 28.0 / 12.0).abs() < 1e-12);
+        assert!((number(&[(17, 1)]).abundancy_index() - 18.0 / 17.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_divisor_count_is_odd() {
+        assert!(number(&[(2, 4)]).divisor_count_is_odd());
+        assert!(number(&[(2, 2), (3, 2)]).divisor_count_is_odd());
+        assert!(!number(&[(2, 2), (3, 1)]).divisor_count_is_odd());
+        for name in ["zero", "one", "prime", "composite"] {
+            let n = test_utils::load_fixture(name);
+            assert_eq!(
+                n.divisor_count_is_odd(),
+                n.divisor_count_mod(2) == 1,
+                "{}",
+                name
+            );
+        }
+        assert!(test_utils::load_fixture("one").divisor_count_is_odd());
+    }
+
+    #[test]
+    fn test_divisor_count_mod() {
+        assert_eq!(number(&[(2, 4)]).divisor_count_mod(2), 1);
+        assert_eq!(number(&[(2, 2), (3, 1)]).divisor_count_mod(2), 0);
+        assert_eq!(number(&[(2, 2), (3, 1)]).divisor_count_mod(4), 2);
+        let huge = number(&[(2, u64::MAX), (3, u64::MAX)]);
+        assert_eq!(
+            BigInt::from(huge.divisor_count_mod(1_000_000_007)),
+            huge.divisor_count().unwrap() % 1_000_000_007
+        );
+        assert_eq!(number(&[(2, 1)]).divisor_count_mod(1), 0);
+    }
+
+    #[test]
+    fn test_without_factor() {
+        let (rest, removed) = number(&[(2, 3), (3, 2), (5, 1)]).without_factor(&BigInt::from(2));
+        assert_eq!(rest.factors(), number(&[(3, 2), (5, 1)]).factors());
+        assert_eq!(rest.status(), &NumberStatus::FullyFactored);
+        assert_eq!(rest.id(), &BigInt::from(-1));
+        assert_eq!(rest.value(), BigInt::from(45));
+        assert_eq!(removed, BigInt::from(8));
+
+        let (rest, removed) = rest.without_factor(&BigInt::from(7));
+        assert_eq!(rest.value(), BigInt::from(45));
+        assert_eq!(removed, BigInt::from(1));
+
+        let (rest, removed) = number(&[(17, 1)]).without_factor(&BigInt::from(17));
+        assert_eq!(rest.status(), &NumberStatus::Unit);
+        assert_eq!(rest.value(), BigInt::from(1));
+        assert_eq!(removed, BigInt::from(17));
+
+        // a single prime left over is prime, not fully factored
+        let (rest, _) = number(&[(2, 2), (3, 1)]).without_factor(&BigInt::from(2));
+        assert_eq!(rest.status(), &NumberStatus::DefinitelyPrime);
+        assert_eq!(rest.id(), &BigInt::from(-1));
+        let (rest, _) = number(&[(2, 1), (3, 2)]).without_factor(&BigInt::from(2));
+        assert_eq!(rest.status(), &NumberStatus::FullyFactored);
+        let (rest, _) = test_utils::load_fixture("large_factor").without_factor(&BigInt::from(3));
+        assert_eq!(rest.status(), &NumberStatus::ProbablyPrime);
+        // the cofactor of a partial factorization may still be composite
+        let (rest, _) = test_utils::load_fixture("partial").without_factor(&BigInt::from(3));
+        assert_eq!(rest.status(), test_utils::load_fixture("partial").status());
+    }
+
+    #[test]
+    fn test_log2_estimate() {
+        assert!((number(&[(2, 10)]).log2_estimate() - 10.0).abs() < 1e-9);
+        let big = number(&[(3, 1000), (5, 1)]);
+        assert!((big.log2_estimate() - 1000.0 * 3f64.log2() - 5f64.log2()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_value_bit_length() {
+        let kibi = number(&[(2, 10)]);
+        assert_eq!(kibi.value_bit_length(), 11);
+        assert_eq!(kibi.value_bit_length(), kibi.value().bits());
+        assert_eq!(kibi.value_bit_length_estimate(), 11);
+        let big = number(&[(3, 1000), (5, 1)]);
+        assert_eq!(big.value_bit_length_estimate(), big.value_bit_length());
+        assert_eq!(test_utils::load_fixture("zero").value_bit_length(), 0);
+        assert_eq!(
+            test_utils::load_fixture("zero").value_bit_length_estimate(),
+            0
+        );
+        assert_eq!(
+            test_utils::load_fixture("one").value_bit_length_estimate(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_factor_entropy() {
+        assert_eq!(number(&[(2, 10)]).factor_entropy(), 0.0);
+        assert!((number(&[(2, 1), (3, 1), (5, 1), (7, 1)]).factor_entropy() - 2.0).abs() < 1e-12);
+        assert!(
+            (number(&[(2, 3), (3, 1)]).factor_entropy() - 0.811_278_124_459_132_8).abs() < 1e-12
+        );
+        assert_eq!(number(&[]).factor_entropy(), 0.0);
+    }
+
+    #[test]
+    fn test_unordered_factor_pair_count() {
+        assert_eq!(
+            number(&[(2, 2), (3, 1)]).unordered_factor_pair_count(),
+            BigInt::from(3)
+        );
+        assert_eq!(
+            number(&[(2, 4)]).unordered_factor_pair_count(),
+            BigInt::from(3)
+        );
+        assert_eq!(
+            number(&[(7, 1)]).unordered_factor_pair_count(),
+            BigInt::from(1)
+        );
+        assert_eq!(number(&[]).unordered_factor_pair_count(), BigInt::from(1));
+    }
+
+    #[test]
+    fn test_multiplicative_partition_count() {
+        let expected = [
+            (number(&[(2, 2), (3, 1)]), 4),
+            (number(&[(7, 1)]), 1),
+            (number(&[(2, 4)]), 5),
+            (number(&[(2, 2), (3, 2)]), 9),
+            (number(&[(2, 1), (3, 1), (5, 1)]), 5),
+            (number(&[(2, 2), (3, 1), (5, 1)]), 11),
+            (number(&[(2, 10)]), 42),
+            (test_utils::load_fixture("one"), 1),
+            (test_utils::load_fixture("prime"), 1),
+        ];
+        for (n, count) in expected {
+            assert_eq!(
+                n.multiplicative_partition_count(),
+                BigInt::from(count),
+                "{}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_divisor_count() {
+        assert_eq!(
+            number(&[(2, 2), (3, 1)]).divisor_count(),
+            Some(BigInt::from(6))
+        );
+        assert_eq!(
+            test_utils::load_fixture("prime").divisor_count(),
+            Some(BigInt::from(2))
+        );
+        assert_eq!(
+            test_utils::load_fixture("one").divisor_count(),
+            Some(BigInt::from(1))
+        );
+        for name in ["zero", "partial"] {
+            assert_eq!(test_utils::load_fixture(name).divisor_count(), None);
+        }
+    }
+
+    #[test]
+    fn test_sum_of_divisors() {
+        let perfect = number(&[(2, 2), (7, 1)]);
+        assert_eq!(perfect.sum_of_divisors(), Some(BigInt::from(56)));
+        assert_eq!(perfect.sum_of_divisors(), Some(perfect.value() * 2));
+        assert_eq!(
+            test_utils::load_fixture("one").sum_of_divisors(),
+            Some(BigInt::from(1))
+        );
+        assert_eq!(test_utils::load_fixture("partial").sum_of_divisors(), None);
+    }
+
+    #[test]
+    fn test_divisor_count_checked() {
+        let twelve = number(&[(2, 2), (3, 1)]);
+        assert_eq!(
+            twelve.divisor_count_checked(&BigInt::from(6)),
+            Ok(BigInt::from(6))
+        );
+        assert!(twelve.divisor_count_checked(&BigInt::from(5)).is_err());
+
+        // 1000 distinct factors, so 2^1000 divisors
+        let factors: Vec<(u64, u64)> = (2..1002).map(|b| (b, 1)).collect();
+        let huge = number(&factors);
+        let count = BigInt::from(1) << 1000;
+        assert_eq!(huge.divisor_count_checked(&count), Ok(count.clone()));
+        let err = huge.divisor_count_checked(&(count >> 1)).unwrap_err();
+        assert_eq!(err.max(), &(BigInt::from(1) << 999));
+    }
+
+    #[test]
+    fn test_sigma() {
+        assert_eq!(number(&[(2, 2), (7, 1)]).sigma(), BigInt::from(56));
+        assert_eq!(number(&[(2, 2), (3, 1)]).sigma(), BigInt::from(28));
+    }
+
+    #[test]
+    fn test_colossal_abundance_score() {
+        let score = |factors: &[(u64, u64)]| number(factors).colossal_abundance_score(0.1).unwrap();
+        let (twelve, ten, six) = (
+            score(&[(2, 2), (3, 1)]),
+            score(&[(2, 1), (5, 1)]),
+            score(&[(2, 1), (3, 1)]),
+        );
+        assert!(twelve > six);
+        assert!(six > ten);
+        let huge = number(&[(2, u64::from(u32::MAX) + 1)]);
+        assert!(matches!(
+            huge.colossal_abundance_score(0.1),
+            Err(FactorDbError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_smarandache() {
+        assert_eq!(number(&[(2, 3)]).smarandache(), BigInt::from(4));
+        assert_eq!(number(&[(2, 2), (3, 1)]).smarandache(), BigInt::from(4));
+        assert_eq!(number(&[(2, 4)]).smarandache(), BigInt::from(6));
+        assert_eq!(number(&[(5, 6)]).smarandache(), BigInt::from(25));
+        assert_eq!(number(&[(7, 1)]).smarandache(), BigInt::from(7));
+        assert_eq!(number(&[]).smarandache(), BigInt::from(1));
+    }
+
+    #[test]
+    fn test_divisors_iter() {
+        let composite = number(&[(2, 3), (3, 2), (5, 1)]);
+        let divisors = composite.divisors_capped(24).unwrap();
+        assert_eq!(composite.divisors_iter().collect::<Vec<_>>(), divisors);
+        assert_eq!(
+            composite.divisors_iter().take(10).collect::<Vec<_>>(),
+            divisors[..10]
+        );
+
+        // 2^1000 divisors, which can't all be listed
+        let factors: Vec<(u64, u64)> = (2..1002).map(|b| (b, 1)).collect();
+        assert_eq!(
+            number(&factors).divisors_iter().take(5).collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5].map(BigInt::from)
+        );
+        assert_eq!(
+            number(&[]).divisors_iter().collect::<Vec<_>>(),
+            [BigInt::from(1)]
+        );
+    }
+
+    #[test]
+    fn test_euler_totient() {
+        assert_eq!(number(&[(2, 1), (5, 1)]).euler_totient(), BigInt::from(4));
+        assert_eq!(
+            number(&[(2, 3), (3, 2), (5, 1)]).euler_totient(),
+            BigInt::from(96)
+        );
+        assert_eq!(number(&[(7, 1)]).euler_totient(), BigInt::from(6));
+        assert_eq!(number(&[]).euler_totient(), BigInt::from(1));
+    }
+
+    #[test]
+    fn test_reduce_exponent_mod_totient() {
+        let hundred = number(&[(2, 2), (5, 2)]);
+        let exp = BigInt::from(10).pow(20) + 3;
+        let reduced = hundred.reduce_exponent_mod_totient(&exp);
+        assert_eq!(reduced, BigInt::from(3));
+        let (a, n) = (BigInt::from(3), BigInt::from(100));
+        assert_eq!(a.modpow(&exp, &n), a.modpow(&reduced, &n));
+        assert_eq!(
+            hundred.reduce_exponent_mod_totient(&BigInt::from(-1)),
+            BigInt::from(39)
+        );
+    }
+
+    #[test]
+    fn test_divisors() {
+        assert_eq!(
+            number(&[(2, 2), (3, 1)]).divisors(),
+            Some([1, 2, 3, 4, 6, 12].map(BigInt::from).to_vec())
+        );
+        assert_eq!(
+            test_utils::load_fixture("prime").divisors(),
+            Some(vec![BigInt::from(1), BigInt::from(17)])
+        );
+        assert_eq!(
+            test_utils::load_fixture("one").divisors(),
+            Some(vec![BigInt::from(1)])
+        );
+        assert_eq!(test_utils::load_fixture("zero").divisors(), None);
+        assert_eq!(test_utils::load_fixture("partial").divisors(), None);
+    }
+
+    #[test]
+    fn test_divisors_capped() {
+        let twelve = number(&[(2, 2), (3, 1)]);
+        assert_eq!(
+            twelve.divisors_capped(6),
+            Ok([1, 2, 3, 4, 6, 12].map(BigInt::from).to_vec())
+        );
+        assert_eq!(
+            twelve.divisors_capped(5).unwrap_err().max(),
+            &BigInt::from(5)
+        );
+        assert_eq!(number(&[]).divisors_capped(1), Ok(vec![BigInt::from(1)]));
+
+        // 2^64 divisors, which would never fit in memory
+        let factors: Vec<(u64, u64)> = (2..66).map(|b| (b, 1)).collect();
+        assert!(number(&factors).divisors_capped(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_factor_graph_edges() {
+        let edges = |pairs: &[(u64, u64)]| -> Vec<(BigInt, BigInt)> {
+            pairs
+                .iter()
+                .map(|&(a, b)| (BigInt::from(a), BigInt::from(b)))
+                .collect()
+        };
+        assert_eq!(
+            number(&[(2, 2), (3, 1)]).factor_graph_edges(),
+            edges(&[(12, 4), (4, 2), (12, 3)])
+        );
+        assert!(number(&[(7, 1)]).factor_graph_edges().is_empty());
+        assert_eq!(number(&[(2, 3)]).factor_graph_edges(), edges(&[(8, 2)]));
+        assert!(test_utils::load_fixture("one")
+            .factor_graph_edges()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_factor_pairs_u64() {
+        assert_eq!(
+            number(&[(2, 3), (3, 2), (5, 1)])
+                .factor_pairs_u64()
+                .unwrap(),
+            vec![(2, 3), (3, 2), (5, 1)]
+        );
+        assert_eq!(
+            number(&[(u64::MAX, u64::MAX)]).factor_pairs_u64().unwrap(),
+            vec![(u64::MAX, u64::MAX)]
+        );
+        let large = test_utils::load_fixture("large_factor");
+        match large.factor_pairs_u64() {
+            Err(FactorDbError::Overflow(n)) => assert_eq!(n, large.factors()[1].base().clone()),
+            other => panic!("expected overflow, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let composite = test_utils::load_fixture("composite");
+        assert_eq!(composite.to_string(), "2 2 2 3 3 5");
+        assert_eq!(format!("{:#}", composite), "360 = 2^3 * 3^2 * 5");
+        assert_eq!(
+            format!("{:#}", test_utils::load_fixture("prime")),
+            "17 = 17"
+        );
+        assert_eq!(format!("{:#}", test_utils::load_fixture("one")), "1 = 1");
+    }
+
+    #[test]
+    fn test_new_factors_since() {
+        let partial = test_utils::load_fixture("partial");
+        let complete: Number = serde_json::from_str(
+            r#"{"id":"1100000004567891234","status":"FF","factors":[["3",1],["1000000007",1],["1000000009",1]]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            complete.new_factors_since(&partial),
+            vec![
+                Factor::new(BigInt::from(1_000_000_007), BigInt::from(1)),
+                Factor::new(BigInt::from(1_000_000_009), BigInt::from(1))
+            ]
+        );
+        assert!(complete.new_factors_since(&complete).is_empty());
+    }
+
+    #[test]
+    fn test_to_pari_matrix() {
+        assert_eq!(
+            number(&[(2, 3), (3, 2), (5, 1)]).to_pari_matrix(),
+            "[2, 3; 3, 2; 5, 1]"
+        );
+        assert_eq!(number(&[(17, 1)]).to_pari_matrix(), "[17, 1]");
+        assert_eq!(number(&[]).to_pari_matrix(), "[;]");
+        assert_eq!(test_utils::load_fixture("one").to_pari_matrix(), "[;]");
+        assert_eq!(test_utils::load_fixture("zero").to_pari_matrix(), "[0, 1]");
+    }
+
+    #[test]
+    fn test_factors_abbreviated() {
+        let large = test_utils::load_fixture("large_factor");
+        assert_eq!(
+            large.factors_abbreviated(10),
+            vec!["3", "61897...62111 (27 digits)"]
+        );
+        assert_eq!(
+            large.factors_abbreviated(27),
+            vec!["3", "618970019642690137449562111"]
+        );
+        assert_eq!(
+            test_utils::load_fixture("composite").factors_abbreviated(1),
+            vec!["2^3", "3^2", "5"]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_stream() {
+        let names = ["zero", "composite", "large_factor"];
+        let expected = names.map(test_utils::load_fixture);
+        let entries = names.map(test_utils::fixture_json);
+        let lines = entries.concat();
+        let array = format!(
+            "[\n  {}\n]\n",
+            entries.each_ref().map(|e| e.trim()).join(",\n  ")
+        );
+        for json in [array, lines, numbers_to_json_array(&expected).unwrap()] {
+            let numbers = parse_json_stream(json.as_bytes())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(numbers, expected, "{}", json);
+        }
+        for empty in ["", " \n", "[]", " [ ] "] {
+            assert_eq!(parse_json_stream(empty.as_bytes()).count(), 0);
+        }
+        for invalid in ["[", "[{}]", r#"[{"id":"1"} {"id":"2"}]"#] {
+            let results: Vec<_> = parse_json_stream(invalid.as_bytes()).collect();
+            assert_eq!(results.len(), 1, "{}", invalid);
+            assert!(matches!(results[0], Err(FactorDbError::ParseError(_))));
+        }
+    }
+
+    #[test]
+    fn test_status_from_str() {
+        let statuses = [
+            NumberStatus::NoFactorsKnown,
+            NumberStatus::FactorsKnown,
+            NumberStatus::FullyFactored,
+            NumberStatus::DefinitelyPrime,
+            NumberStatus::ProbablyPrime,
+            NumberStatus::Unknown,
+            NumberStatus::Unit,
+            NumberStatus::Zero,
+            NumberStatus::NotInDatabase,
+        ];
+        for status in statuses {
+            let code = status.to_string();
+            assert_eq!(
+                serde_json::to_string(&status).unwrap(),
+                format!("\"{}\"", code)
+            );
+            assert_eq!(code.parse::<NumberStatus>(), Ok(status));
+        }
+        assert_eq!("PRP".parse(), Ok(NumberStatus::ProbablyPrime));
+        assert_eq!(
+            "ff".parse::<NumberStatus>(),
+            Err(ParseStatusError("ff".to_string()))
+        );
+        assert!("".parse::<NumberStatus>().is_err());
+    }
+
+    #[test]
+    fn test_json_array() {
+        let numbers = ["composite", "large_factor"].map(test_utils::load_fixture);
+        let json = numbers_to_json_array(&numbers).unwrap();
+        assert!(json.starts_with(r#"[{"id":"#));
+        assert_eq!(numbers_from_json_array(&json).unwrap(), numbers);
+        assert_eq!(numbers_to_json_array(&[]).unwrap(), "[]");
+        assert!(matches!(
+            numbers_from_json_array(r#"{"id":"1"}"#),
+            Err(FactorDbError::ParseError(_))
+        ));
+    }
+}
@@ -0,0 +1,69 @@
+use std::fmt::{Display, Formatter};
+
+use num_bigint::BigInt;
+
+/// A query for [`FactorDbClient::get()`](crate::FactorDbClient::get) and friends, which accept anything implementing
+/// [`Display`].
+///
+/// Use [`Query::NumberWithLabel`] if the value of an expression is already known, so FactorDB doesn't have to evaluate
+/// it again while the expression is kept around for display.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Query {
+    /// An expression for FactorDB to evaluate, e.g. `2^64-1`.
+    Expression(String),
+    /// A number which is sent in decimal, along with a label (e.g. the expression it came from) which isn't.
+    NumberWithLabel {
+        /// The number to look up.
+        value: BigInt,
+        /// How to show the number to humans.
+        label: String,
+    },
+}
+
+impl Query {
+    /// Returns how to show the query to humans, i.e. the expression or the label.
+    pub fn label(&self) -> &str {
+        match self {
+            Query::Expression(expression) => expression,
+            Query::NumberWithLabel { label, .. } => label,
+        }
+    }
+}
+
+/// Formats the query the way it is sent to FactorDB, i.e. the expression or the value in decimal.
+impl Display for Query {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Query::Expression(expression) => write!(f, "{}", expression),
+            Query::NumberWithLabel { value, .. } => write!(f, "{}", value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[tokio::test]
+    async fn test_number_with_label() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = test_utils::mock_query(&mut server, "1024", &test_utils::entry(1024))
+            .expect(1)
+            .create_async()
+            .await;
+        let client = test_utils::client(&server);
+        let query = Query::NumberWithLabel {
+            value: BigInt::from(1024),
+            label: "2^10".to_string(),
+        };
+        let number = client.get(&query).await.unwrap();
+        assert_eq!(number.value(), BigInt::from(1024));
+        assert_eq!(query.label(), "2^10");
+        mock.assert_async().await;
+
+        let query = Query::Expression("2^10".to_string());
+        assert_eq!(query.to_string(), "2^10");
+        assert_eq!(query.label(), "2^10");
+    }
+}
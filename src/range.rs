@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use futures::{stream, StreamExt, TryStreamExt};
+use num_bigint::BigInt;
+
+use crate::client::DEFAULT_CONCURRENCY;
+use crate::{Factor, FactorDbClient, FactorDbError, NumberStatus};
+
+/// Computes the Mertens function `M(n)`, i.e. the sum of [`Number::mobius()`](crate::Number::mobius) for every
+/// integer from 1 to `n`.
+///
+/// This looks up every one of those `n` integers in FactorDB, which gets slow (and impolite to the server) very quickly
+/// as `n` grows. Up to 8 requests are in flight at a time; use [`mertens_with_concurrency()`] to change that.
+///
+/// # Errors
+/// Returns a [`FactorDbError`] if any of the requests fails.
+pub async fn mertens(client: &FactorDbClient, n: u64) -> Result<i64, FactorDbError> {
+    mertens_with_concurrency(client, n, DEFAULT_CONCURRENCY).await
+}
+
+/// Same as [`mertens()`], but with up to `limit` requests in flight at a time.
+///
+/// # Errors
+/// Returns a [`FactorDbError`] if any of the requests fails.
+pub async fn mertens_with_concurrency(
+    client: &FactorDbClient,
+    n: u64,
+    limit: usize,
+) -> Result<i64, FactorDbError> {
+    stream::iter(1..=n)
+        .map(|k| client.get(k))
+        .buffer_unordered(limit.max(1))
+        .try_fold(0, |sum, number| async move {
+            Ok(sum + i64::from(number.mobius()))
+        })
+        .await
+}
+
+/// Computes the least common multiple of every integer in `range`, e.g. `lcm(1..=10) = 2520`.
+///
+/// Rather than folding pairwise lcm operations, this looks up the factorization of every integer in the range and
+/// takes the highest exponent of each prime, so it makes one request per integer. Up to 8 requests are in flight at a
+/// time; use [`lcm_of_range_with_concurrency()`] to change that.
+///
+/// # Errors
+/// Returns a [`FactorDbError`] if any of the requests fails.
+pub async fn lcm_of_range(
+    client: &FactorDbClient,
+    range: RangeInclusive<u64>,
+) -> Result<BigInt, FactorDbError> {
+    lcm_of_range_with_concurrency(client, range, DEFAULT_CONCURRENCY).await
+}
+
+/// Same as [`lcm_of_range()`], but with up to `limit` requests in flight at a time.
+///
+/// # Errors
+/// Returns a [`FactorDbError`] if any of the requests fails.
+pub async fn lcm_of_range_with_concurrency(
+    client: &FactorDbClient,
+    range: RangeInclusive<u64>,
+    limit: usize,
+) -> Result<BigInt, FactorDbError> {
+    // highest exponent of each prime so far, or `None` once 0 shows up (which makes the lcm 0)
+    let exponents = stream::iter(range)
+        .map(|k| client.get(k))
+        .buffer_unordered(limit.max(1))
+        .try_fold(Some(BTreeMap::new()), |exponents, number| async move {
+            let Some(mut exponents) = exponents else {
+                return Ok(None);
+            };
+            if number.status() == &NumberStatus::Zero {
+                return Ok(None);
+            }
+            for factor in number.factors() {
+                let exponent = exponents
+                    .entry(factor.base().clone())
+                    .or_insert_with(|| BigInt::from(0));
+                if factor.exponent() > exponent {
+                    *exponent = factor.exponent().clone();
+                }
+            }
+            Ok(Some(exponents))
+        })
+        .await?;
+    Ok(match exponents {
+        Some(exponents) => exponents
+            .into_iter()
+            .map(|(base, exponent)| Factor::new(base, exponent).pow())
+            .product(),
+        None => BigInt::from(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[tokio::test]
+    async fn test_mertens() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 1..=10).await;
+        let client = test_utils::client(&server);
+        assert_eq!(mertens(&client, 10).await.unwrap(), -1);
+        assert_eq!(mertens_with_concurrency(&client, 4, 1).await.unwrap(), -1);
+    }
+
+    #[tokio::test]
+    async fn test_lcm_of_range() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_range(&mut server, 0..=10).await;
+        let client = test_utils::client(&server);
+        assert_eq!(
+            lcm_of_range(&client, 1..=10).await.unwrap(),
+            BigInt::from(2520)
+        );
+        assert_eq!(
+            lcm_of_range_with_concurrency(&client, 4..=6, 1)
+                .await
+                .unwrap(),
+            BigInt::from(60)
+        );
+        assert_eq!(lcm_of_range(&client, 0..=3).await.unwrap(), BigInt::from(0));
+    }
+}
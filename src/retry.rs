@@ -0,0 +1,82 @@
+//! Contains the policy for retrying requests which failed for transient reasons.
+
+use std::time::Duration;
+
+use crate::FactorDbError;
+
+/// How [`FactorDbClient::get_with_retry()`](crate::FactorDbClient::get_with_retry) retries failed requests.
+///
+/// Only transient failures are retried (see [`FactorDbError::is_retryable()`]): rate limiting (HTTP 429), server errors
+/// (HTTP 5xx), and requests which failed to connect, timed out or had their connection reset. Terminal errors such as
+/// [`FactorDbError::InvalidNumber`] or [`FactorDbError::ParseError`] are returned straight away, since sending the same
+/// request again won't fix them.
+///
+/// The delay before the `n`th retry is `base_delay * 2^(n - 1)`, capped at `max_delay`. If FactorDB says how long to
+/// wait with a `Retry-After` header, that takes precedence, but is capped at `max_delay` as well so that a misbehaving
+/// server can't stall a request for hours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// How many times to retry a request after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for every retry after that
+    pub base_delay: Duration,
+    /// Upper bound of the delay between retries, including delays requested with `Retry-After`
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// How long to wait before retrying after `retries` previous retries, the last of which failed with `error`.
+    pub(crate) fn delay(&self, retries: u32, error: &FactorDbError) -> Duration {
+        if let FactorDbError::RateLimited {
+            retry_after: Some(seconds),
+        } = error
+        {
+            return Duration::from_secs(*seconds).min(self.max_delay);
+        }
+        2u32.checked_pow(retries)
+            .and_then(|factor| self.base_delay.checked_mul(factor))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+/// Retries up to 3 times, starting with a 500 ms delay and waiting at most 30 seconds between retries.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+        let error = FactorDbError::ServerError(StatusCode::SERVICE_UNAVAILABLE);
+        let delays: Vec<_> = (0..4).map(|n| policy.delay(n, &error).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 5]);
+        assert_eq!(policy.delay(40, &error), policy.max_delay);
+        let error = FactorDbError::RateLimited {
+            retry_after: Some(3),
+        };
+        assert_eq!(policy.delay(0, &error), Duration::from_secs(3));
+        let error = FactorDbError::RateLimited {
+            retry_after: Some(60),
+        };
+        assert_eq!(policy.delay(0, &error), policy.max_delay);
+        let error = FactorDbError::RateLimited {
+            retry_after: Some(u64::MAX),
+        };
+        assert_eq!(policy.delay(0, &error), policy.max_delay);
+    }
+}
@@ -0,0 +1,145 @@
+//! Contains a helper for submitting many numbers and waiting for FactorDB to factor them.
+
+use std::fmt::Display;
+
+use crate::client::DEFAULT_CONCURRENCY;
+use crate::{FactorDbClient, FactorDbError, Number};
+
+/// Keeps track of numbers submitted to FactorDB until their factorization is complete.
+///
+/// Looking up a number adds it to FactorDB, which then works on factoring it in the background. A session submits
+/// numbers that way, and [`Self::poll_all()`] looks all of them up again, handing back the ones which can't be factored
+/// any further (i.e. fully factored, (probably) prime, or 0 or 1) and keeping the rest [pending](Self::pending()).
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::error::Error;
+/// # use std::time::Duration;
+/// use factordb::{FactorDbClient, FactoringSession};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let mut session = FactoringSession::new(FactorDbClient::new());
+/// session.submit("2^128+1");
+/// session.submit("2^256+1");
+///
+/// while !session.pending().is_empty() {
+///     for (query, result) in session.poll_all().await {
+///         println!("{}: {}", query, result?);
+///     }
+///     tokio::time::sleep(Duration::from_secs(60)).await;
+/// }
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FactoringSession {
+    client: FactorDbClient,
+    pending: Vec<String>,
+    limit: usize,
+}
+
+impl FactoringSession {
+    /// Creates an empty session using `client`, with up to 8 requests in flight at a time when polling.
+    pub fn new(client: FactorDbClient) -> Self {
+        Self::with_concurrency(client, DEFAULT_CONCURRENCY)
+    }
+
+    /// Same as [`Self::new()`], but with up to `limit` requests in flight at a time when polling.
+    pub fn with_concurrency(client: FactorDbClient, limit: usize) -> Self {
+        Self {
+            client,
+            pending: vec![],
+            limit: limit.max(1),
+        }
+    }
+
+    /// Adds `number` to the session. Submitting a number which is already pending does nothing.
+    ///
+    /// The number isn't sent to FactorDB until the next [`Self::poll_all()`].
+    pub fn submit<T: Display>(&mut self, number: T) {
+        let query = number.to_string();
+        if !self.pending.contains(&query) {
+            self.pending.push(query);
+        }
+    }
+
+    /// Returns the queries which haven't completed yet, in the order they were submitted.
+    pub fn pending(&self) -> &[String] {
+        &self.pending
+    }
+
+    /// Looks up every pending number, returning the ones whose factorization is complete along with their queries.
+    ///
+    /// Failed requests are returned as well, and like completed numbers, are no longer pending afterwards; submit them
+    /// again to keep polling them. Everything else stays pending for the next poll.
+    pub async fn poll_all(&mut self) -> Vec<(String, Result<Number, FactorDbError>)> {
+        let queries = std::mem::take(&mut self.pending);
+        let results = self
+            .client
+            .get_many_with_concurrency(&queries, self.limit)
+            .await;
+        let mut done = vec![];
+        for (query, result) in queries.into_iter().zip(results) {
+            match result {
+                Ok(number) if !number.is_factorization_complete() => self.pending.push(query),
+                result => done.push((query, result)),
+            }
+        }
+        done
+    }
+
+    /// Returns the client used by this session.
+    pub fn client(&self) -> &FactorDbClient {
+        &self.client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    #[tokio::test]
+    async fn test_poll_all() {
+        let mut server = mockito::Server::new_async().await;
+        test_utils::mock_query(
+            &mut server,
+            "91",
+            r#"{"id":"91","status":"C","factors":[["91",1]]}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+        test_utils::mock_range(&mut server, 15..=15).await;
+        test_utils::mock_range(&mut server, 91..=91).await;
+        test_utils::mock_query(&mut server, "AAAAA", "")
+            .with_status(404)
+            .create_async()
+            .await;
+        let mut session = FactoringSession::new(test_utils::client(&server));
+        for query in ["91", "15", "AAAAA", "15"] {
+            session.submit(query);
+        }
+        assert_eq!(session.pending(), ["91", "15", "AAAAA"]);
+
+        let done = session.poll_all().await;
+        assert_eq!(done.len(), 2);
+        assert_eq!(done[0].0, "15");
+        assert_eq!(done[0].1.as_ref().unwrap().value(), 15.into());
+        assert!(matches!(done[1], (_, Err(FactorDbError::InvalidNumber))));
+        assert_eq!(session.pending(), ["91"]);
+
+        let done = session.poll_all().await;
+        assert_eq!(done.len(), 1);
+        assert_eq!(done[0].0, "91");
+        assert_eq!(
+            done[0].1.as_ref().unwrap().status(),
+            &crate::NumberStatus::FullyFactored
+        );
+        assert!(session.pending().is_empty());
+        assert!(session.poll_all().await.is_empty());
+    }
+}
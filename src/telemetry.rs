@@ -0,0 +1,110 @@
+//! Metrics recorded through the [`metrics`] facade.
+
+use std::time::Duration;
+
+use metrics::{counter, histogram};
+
+#[cfg(feature = "cache")]
+use crate::CacheStatus;
+use crate::FactorDbError;
+
+/// Records a request to FactorDB which took `duration` and failed with `error`, if any.
+pub(crate) fn record_request(duration: Duration, error: Option<&FactorDbError>) {
+    counter!("factordb_requests_total").increment(1);
+    histogram!("factordb_request_duration_seconds").record(duration.as_secs_f64());
+    if let Some(error) = error {
+        counter!("factordb_errors_total", "error" => error_name(error)).increment(1);
+    }
+}
+
+/// Records a lookup in the cache of a [`CachedFactorDbClient`](crate::CachedFactorDbClient).
+#[cfg(feature = "cache")]
+pub(crate) fn record_cache(status: CacheStatus) {
+    match status {
+        CacheStatus::Hit => counter!("factordb_cache_hits_total").increment(1),
+        CacheStatus::Miss => counter!("factordb_cache_misses_total").increment(1),
+    }
+}
+
+fn error_name(error: &FactorDbError) -> &'static str {
+    match error {
+        FactorDbError::RequestError(_) => "request",
+        FactorDbError::InvalidNumber => "invalid_number",
+        FactorDbError::Timeout => "timeout",
+        FactorDbError::RateLimited { .. } => "rate_limited",
+        FactorDbError::ServerError(_) => "server_error",
+        FactorDbError::UnexpectedStatus(_) => "unexpected_status",
+        FactorDbError::ParseError(_) => "parse",
+        FactorDbError::DecodeError(_) => "decode",
+        FactorDbError::ResponseTooLarge(_) => "response_too_large",
+        // these come from configuring a client or checking a result, never from sending a request
+        FactorDbError::InvalidEndpoint(_)
+        | FactorDbError::InvalidEnvVar(_)
+        | FactorDbError::NotFullyFactored(_)
+        | FactorDbError::Overflow(_) => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use crate::test_utils;
+
+    #[test]
+    fn test_request_metrics() {
+        let mut server = mockito::Server::new();
+        test_utils::mock_query(&mut server, "15", &test_utils::entry(15)).create();
+        test_utils::mock_query(&mut server, "bad", "")
+            .with_status(404)
+            .create();
+        test_utils::mock_query(&mut server, "html", "<html></html>").create();
+        let client = test_utils::client(&server);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            runtime.block_on(async {
+                client.get(15).await.unwrap();
+                client.get(15).await.unwrap();
+                client.get("bad").await.unwrap_err();
+                client.get("html").await.unwrap_err();
+            })
+        });
+
+        let metrics: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let labels: Vec<_> = key.key().labels().map(|l| l.value().to_string()).collect();
+                (key.key().name().to_string(), labels, value)
+            })
+            .collect();
+        let value = |name: &str, labels: &[&str]| {
+            metrics
+                .iter()
+                .find(|(key, l, _)| key == name && l == labels)
+                .map(|(_, _, value)| value)
+        };
+        assert_eq!(
+            value("factordb_requests_total", &[]),
+            Some(&DebugValue::Counter(4))
+        );
+        assert_eq!(
+            value("factordb_errors_total", &["invalid_number"]),
+            Some(&DebugValue::Counter(1))
+        );
+        // parse errors only show up after the response was received
+        assert_eq!(
+            value("factordb_errors_total", &["parse"]),
+            Some(&DebugValue::Counter(1))
+        );
+        assert!(
+            matches!(value("factordb_request_duration_seconds", &[]), Some(DebugValue::Histogram(h)) if h.len() == 4)
+        );
+    }
+}
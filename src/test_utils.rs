@@ -0,0 +1,58 @@
+//! Helpers shared by the offline tests.
+
+use std::fs;
+use std::path::Path;
+
+use crate::Number;
+
+#[cfg(feature = "client")]
+mod mock;
+
+#[cfg(feature = "client")]
+pub(crate) use mock::*;
+
+/// Loads one of the canned FactorDB responses in `tests/fixtures`, e.g. `load_fixture("prime")`.
+///
+/// The available fixtures are `zero`, `one`, `prime` (17), `composite` (360), `partial` (a partially factored
+/// number) and `large_factor` (a number with a 27 digit prime factor, too large for a [`u64`]).
+pub(crate) fn load_fixture(name: &str) -> Number {
+    serde_json::from_str(&fixture_json(name)).expect("invalid fixture")
+}
+
+/// Returns the raw JSON of a fixture loaded by [`load_fixture()`].
+pub(crate) fn fixture_json(name: &str) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{}.json", name));
+    fs::read_to_string(path).expect("missing fixture")
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigInt;
+
+    use super::*;
+    use crate::NumberStatus;
+
+    #[test]
+    fn test_fixtures() {
+        let expected = [
+            ("zero", NumberStatus::Zero, 1),
+            ("one", NumberStatus::Unit, 1),
+            ("prime", NumberStatus::DefinitelyPrime, 1),
+            ("composite", NumberStatus::FullyFactored, 3),
+            ("partial", NumberStatus::FactorsKnown, 2),
+            ("large_factor", NumberStatus::FullyFactored, 2),
+        ];
+        for (name, status, factor_count) in expected {
+            let number = load_fixture(name);
+            assert_eq!(number.status(), &status, "{}", name);
+            assert_eq!(number.factors().len(), factor_count, "{}", name);
+        }
+        assert_eq!(load_fixture("one").id(), &BigInt::from(1));
+        assert_eq!(
+            load_fixture("large_factor").factors()[1].base().to_string(),
+            "618970019642690137449562111"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Helpers for tests which run against a mock FactorDB server instead of the real one.
+
+use std::ops::RangeInclusive;
+
+use mockito::{Matcher, Mock, ServerGuard};
+
+use num_bigint::BigInt;
+
+use crate::factor::factor_local;
+use crate::FactorDbClient;
+
+/// Creates a client which sends its requests to the mock `server`.
+pub(crate) fn client(server: &ServerGuard) -> FactorDbClient {
+    FactorDbClient::with_endpoint(format!("{}/api", server.url())).unwrap()
+}
+
+/// Starts building a mock responding to a query for `query` with `body`.
+pub(crate) fn mock_query(server: &mut ServerGuard, query: &str, body: &str) -> Mock {
+    server
+        .mock("GET", "/api")
+        .match_query(Matcher::UrlEncoded("query".into(), query.into()))
+        .with_header("content-type", "application/json")
+        .with_body(body)
+}
+
+/// Mocks a FactorDB entry for every number in `range`, factored by trial division.
+pub(crate) async fn mock_range(server: &mut ServerGuard, range: RangeInclusive<u64>) {
+    for n in range {
+        mock_query(server, &n.to_string(), &entry(n))
+            .create_async()
+            .await;
+    }
+}
+
+/// Builds the JSON FactorDB would respond with for a small number.
+pub(crate) fn entry(n: u64) -> String {
+    let factors = factor_local(n);
+    let status = match n {
+        0 => "Zero",
+        1 => "Unit",
+        _ if factors.len() == 1 && factors[0].exponent() == &BigInt::from(1) => "P",
+        _ => "FF",
+    };
+    let factors: Vec<String> = factors
+        .iter()
+        .map(|f| format!(r#"["{}",{}]"#, f.base(), f.exponent()))
+        .collect();
+    format!(
+        r#"{{"id":"{}","status":"{}","factors":[{}]}}"#,
+        n,
+        status,
+        factors.join(",")
+    )
+}
@@ -1,6 +1,7 @@
 use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 use serde::de::Unexpected;
-use serde::{de, Deserializer};
+use serde::{de, ser, Deserialize, Deserializer, Serializer};
 use std::fmt;
 use std::str::FromStr;
 
@@ -61,3 +62,98 @@ where
 {
     deserializer.deserialize_u64(DeserializeToBigIntVisitor)
 }
+
+/// deserialize a Vec and sort it, so that code using it can rely on the order
+pub(crate) fn deserialize_sorted<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Ord,
+{
+    let mut v = Vec::<T>::deserialize(deserializer)?;
+    v.sort();
+    Ok(v)
+}
+
+/// serialize bigint to String, the way FactorDB sends ids and factors
+pub(crate) fn serialize_bigint_to_string<S>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&n.to_string())
+}
+
+/// serialize bigint to u64, the way FactorDB sends exponents
+pub(crate) fn serialize_bigint_to_u64<S>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let n = n
+        .to_u64()
+        .ok_or_else(|| ser::Error::custom(format!("{} does not fit in a u64", n)))?;
+    serializer.serialize_u64(n)
+}
+
+/// Checks that `endpoint` is a valid HTTP(S) URL.
+#[cfg(feature = "client")]
+pub(crate) fn validate_endpoint(endpoint: String) -> Result<String, crate::FactorDbError> {
+    match reqwest::Url::parse(&endpoint) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Ok(endpoint),
+        _ => Err(crate::FactorDbError::InvalidEndpoint(endpoint)),
+    }
+}
+
+/// Turns an error sending a request into a [`FactorDbError`](crate::FactorDbError), telling timeouts apart.
+#[cfg(feature = "client")]
+pub(crate) fn request_error(e: reqwest::Error) -> crate::FactorDbError {
+    if e.is_timeout() {
+        crate::FactorDbError::Timeout
+    } else {
+        crate::FactorDbError::RequestError(e)
+    }
+}
+
+/// Same as [`request_error()`], but for errors reading the response body.
+#[cfg(feature = "client")]
+pub(crate) fn body_error(e: reqwest::Error) -> crate::FactorDbError {
+    if e.is_timeout() {
+        crate::FactorDbError::Timeout
+    } else {
+        crate::FactorDbError::DecodeError(e)
+    }
+}
+
+/// Turns an unsuccessful HTTP status into the matching error.
+#[cfg(feature = "client")]
+pub(crate) fn check_status(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> Result<(), crate::FactorDbError> {
+    if status.is_success() {
+        Ok(())
+    } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        // Retry-After can also be an HTTP date, which isn't worth supporting here
+        let retry_after = headers
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok());
+        Err(crate::FactorDbError::RateLimited { retry_after })
+    } else if status.is_client_error() {
+        Err(crate::FactorDbError::InvalidNumber)
+    } else if status.is_server_error() {
+        Err(crate::FactorDbError::ServerError(status))
+    } else {
+        Err(crate::FactorDbError::UnexpectedStatus(status))
+    }
+}
+
+/// Base 2 logarithm of a (positive) [`BigInt`] which may be too large to fit in a `f64`.
+pub(crate) fn log2(n: &BigInt) -> f64 {
+    let bits = n.bits();
+    if bits <= 1000 {
+        n.to_f64().unwrap_or(f64::NAN).log2()
+    } else {
+        // keep the 64 most significant bits, the rest doesn't matter at f64 precision anyway
+        let shift = bits - 64;
+        (n >> shift).to_f64().unwrap_or(f64::NAN).log2() + shift as f64
+    }
+}
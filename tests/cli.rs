@@ -0,0 +1,85 @@
+//! Runs the `factordb` binary against a mock FactorDB server.
+
+use std::process::{Command, Output};
+
+use mockito::{Matcher, Mock, ServerGuard};
+
+/// Mocks the response to a query for `query`.
+fn mock_query(server: &mut ServerGuard, query: &str, body: &str) -> Mock {
+    server
+        .mock("GET", "/api")
+        .match_query(Matcher::UrlEncoded("query".into(), query.into()))
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .create()
+}
+
+/// Runs the binary with `args`, sending its requests to `server`.
+fn factordb(server: &ServerGuard, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_factordb"))
+        .arg("--endpoint")
+        .arg(format!("{}/api", server.url()))
+        .args(args)
+        .output()
+        .expect("failed to run factordb")
+}
+
+fn stdout(output: &Output) -> &str {
+    assert!(output.status.success(), "{:?}", output);
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_output_formats() {
+    let mut server = mockito::Server::new();
+    mock_query(
+        &mut server,
+        "100",
+        r#"{"id":"100","status":"FF","factors":[["2",2],["5",2]]}"#,
+    );
+    assert_eq!(stdout(&factordb(&server, &["100"])), "2 2 5 5\n");
+    assert_eq!(
+        stdout(&factordb(&server, &["--exponents", "100"])),
+        "2^2 5^2\n"
+    );
+    assert_eq!(stdout(&factordb(&server, &["--unique", "100"])), "2 5\n");
+    assert_eq!(
+        stdout(&factordb(&server, &["--csv", "100"])),
+        "input,status,factors\n100,FF,\"2 2 5 5\"\n"
+    );
+}
+
+#[test]
+fn test_invalid_number() {
+    let mut server = mockito::Server::new();
+    server
+        .mock("GET", "/api")
+        .match_query(Matcher::UrlEncoded("query".into(), "AAAAA".into()))
+        .with_status(404)
+        .create();
+    let output = factordb(&server, &["AAAAA"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("AAAAA: Invalid number"));
+}
+
+#[test]
+fn test_verify_expression() {
+    let mut server = mockito::Server::new();
+    // missing a factor of 2
+    mock_query(
+        &mut server,
+        "2^10",
+        r#"{"id":"1024","status":"FF","factors":[["2",9]]}"#,
+    );
+    mock_query(
+        &mut server,
+        "2^5-15",
+        r#"{"id":"17","status":"P","factors":[["17",1]]}"#,
+    );
+    let output = factordb(&server, &["--verify", "2^10", "2^5-15"]);
+    assert_eq!(stdout(&output), "2 2 2 2 2 2 2 2 2\n17\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "warning: 2^10: factors multiply to 512 instead of 1024\n"
+    );
+}
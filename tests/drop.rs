@@ -0,0 +1,136 @@
+//! Checks that dropping clients releases everything they hold.
+//!
+//! These run in their own test binary, since the open file descriptors and threads are counted for the whole process
+//! and other tests would change them while these are running.
+
+#![cfg(target_os = "linux")]
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use factordb::FactorDbClient;
+
+/// Serializes the tests in this file, which would otherwise count each other's resources.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Number of clients each test creates, sends requests with and drops.
+const CLIENTS: usize = 20;
+
+/// Starts a server answering every request with the entry for 15 and returns its API endpoint.
+///
+/// Unlike mockito, which closes every connection after one response, this keeps connections alive until the client
+/// hangs up, so they stay in the client's connection pool until it's dropped.
+fn serve() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}/api", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            std::thread::spawn(move || serve_connection(stream));
+        }
+    });
+    endpoint
+}
+
+/// Answers requests on `stream` until the client closes it.
+fn serve_connection(mut stream: TcpStream) {
+    let body = r#"{"id":"15","status":"FF","factors":[["3",1],["5",1]]}"#;
+    let response = format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    let mut request = vec![];
+    let mut buf = [0; 1024];
+    loop {
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+        // requests are GETs without a body, so each one ends with the headers
+        while let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            request.drain(..end + 4);
+            if stream.write_all(response.as_bytes()).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Number of entries in `dir`, e.g. open file descriptors or threads of this process.
+fn count_entries(dir: &str) -> usize {
+    std::fs::read_dir(dir).unwrap().count()
+}
+
+/// Waits up to a few seconds for `count` to drop back to `baseline`, since the server side of a connection is closed
+/// asynchronously after the client hangs up.
+fn settles_to(baseline: usize, count: impl Fn() -> usize) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while count() != baseline {
+        if Instant::now() > deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    true
+}
+
+#[test]
+fn test_drop_leaves_no_tasks() {
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let metrics = runtime.metrics();
+    let endpoint = serve();
+    let tasks = metrics.num_alive_tasks();
+    let fds = count_entries("/proc/self/fd");
+
+    runtime.block_on(async {
+        for _ in 0..CLIENTS {
+            let client = FactorDbClient::with_endpoint(&endpoint).unwrap();
+            let cloned = client.clone();
+            assert!(client.get(15).await.is_ok());
+            assert!(cloned.get_json(15).await.is_ok());
+            drop(client);
+            drop(cloned);
+        }
+    });
+
+    // connection tasks shut down on their own once the pool holding them is gone
+    assert!(
+        settles_to(tasks, || metrics.num_alive_tasks()),
+        "leaked tasks"
+    );
+    assert!(
+        settles_to(fds, || count_entries("/proc/self/fd")),
+        "leaked file descriptors"
+    );
+}
+
+#[cfg(feature = "blocking")]
+#[test]
+fn test_drop_blocking() {
+    use factordb::FactorDbBlockingClient;
+
+    let _lock = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let endpoint = serve();
+    let fds = count_entries("/proc/self/fd");
+    let threads = count_entries("/proc/self/task");
+
+    // each client owns a runtime thread which must be joined on drop, otherwise this would pile up threads
+    for _ in 0..CLIENTS {
+        let client = FactorDbBlockingClient::with_endpoint(&endpoint).unwrap();
+        assert!(client.get(15).is_ok());
+        drop(client);
+    }
+
+    assert!(
+        settles_to(threads, || count_entries("/proc/self/task")),
+        "leaked threads"
+    );
+    assert!(
+        settles_to(fds, || count_entries("/proc/self/fd")),
+        "leaked file descriptors"
+    );
+}